@@ -0,0 +1,118 @@
+use street_of_zombies::game_entity::ennemies::{Ennemy, EnnemyKind};
+use street_of_zombies::game_entity::health::Health;
+use street_of_zombies::game_entity::projectiles::Projectile;
+use street_of_zombies::game_system::game_config::{EnnemyConfig, GameConfig};
+use street_of_zombies::game_system::gameplay_events::{EnnemyHit, PlayerHit};
+use street_of_zombies::game_system::math_and_generator::{self, GameRng};
+use street_of_zombies::game_system::projectile_and_kill_gameplay::{projectile_collision_system, projectile_movement_system};
+use street_of_zombies::game_system::spatial_grid::{rebuild_spatial_grid_system, SpatialGrid};
+use street_of_zombies::game_system::GAMEPLAY_TICKS_PER_SECOND;
+
+use bevy::ecs::schedule::ExecutorKind;
+use bevy::prelude::*;
+
+use std::time::{Duration, Instant};
+
+/// Number of projectiles of the benchmark, a few shotguns firing at a crowd
+static BENCHMARK_PROJECTILES: usize = 1000;
+/// Number of ennemies of the benchmark
+static BENCHMARK_ENNEMIES: usize = 200;
+/// Number of gameplay ticks simulated by each method
+static BENCHMARK_ITERATIONS: u32 = 200;
+/// The same entities are generated at each run
+static BENCHMARK_SEED: u64 = 16;
+/// A single cell covers the whole game area: every projectile is checked against every ennemy
+static SINGLE_CELL_SIZE: f32 = 100_000.;
+
+/// Create a world with the projectiles and the ennemies spread over the game area, and the gameplay tick
+/// moving the projectiles then checking their collisions, like in the game
+///
+/// # Arguments
+///
+/// * `spatial_grid` - The collision grid used by the tick
+///
+fn create_collision_tick(spatial_grid: SpatialGrid) -> (World, Schedule) {
+    let mut world = World::new();
+    let mut time = Time::<()>::default();
+    time.advance_by(Duration::from_secs_f64(1.0 / GAMEPLAY_TICKS_PER_SECOND));
+    world.insert_resource(time);
+    world.init_resource::<GameConfig>();
+    world.init_resource::<Events<EnnemyHit>>();
+    world.init_resource::<Events<PlayerHit>>();
+    world.insert_resource(spatial_grid);
+
+    let mut game_rng = GameRng::new(BENCHMARK_SEED);
+    let game_area = GameConfig::default().game_area;
+    let mut generate_position = || {
+        math_and_generator::generate_random_position(game_rng.generator(), game_area.limit_x, game_area.limit_y)
+    };
+
+    for _ in 0..BENCHMARK_ENNEMIES {
+        let position = generate_position();
+        world.spawn((
+            Ennemy::new(&EnnemyConfig::default(), EnnemyKind::Walker, Vec2::new(0., 1.), position),
+            Health::new(EnnemyConfig::default().walker.health),
+            Transform::from_translation(position.extend(0.)),
+        ));
+    }
+    for _ in 0..BENCHMARK_PROJECTILES {
+        let position = generate_position();
+        world.spawn((
+            Projectile::new(500., Vec2::new(0., 1.), position, 500, false),
+            Transform::from_translation(position.extend(0.)),
+        ));
+    }
+
+    let mut schedule = Schedule::default();
+    schedule
+        .set_executor_kind(ExecutorKind::SingleThreaded)
+        .add_systems((projectile_movement_system, rebuild_spatial_grid_system, projectile_collision_system).chain());
+    schedule
+        .initialize(&mut world)
+        .expect("The collision tick cannot be built");
+
+    (world, schedule)
+}
+
+/// Measure the average duration of a gameplay tick, a new world is created (and not measured) for each tick
+///
+/// # Arguments
+///
+/// * `create_spatial_grid` - Create the collision grid used by the tick
+///
+fn measure<F: Fn() -> SpatialGrid>(create_spatial_grid: F) -> (Duration, usize) {
+    let mut total_duration = Duration::ZERO;
+    let mut number_of_hits = 0;
+
+    for _ in 0..BENCHMARK_ITERATIONS {
+        let (mut world, mut schedule) = create_collision_tick(create_spatial_grid());
+
+        let start = Instant::now();
+        schedule.run(&mut world);
+        total_duration += start.elapsed();
+
+        number_of_hits = world.resource::<Events<EnnemyHit>>().len();
+    }
+
+    (total_duration / BENCHMARK_ITERATIONS, number_of_hits)
+}
+
+/// Compare the collision checks of a tick with the spatial grid of the game and with a single cell, then print the results
+fn main() {
+    let (single_cell_duration, single_cell_hits) = measure(|| SpatialGrid::new(SINGLE_CELL_SIZE));
+    let (grid_duration, grid_hits) = measure(SpatialGrid::default);
+
+    // The grid only skips the ennemies out of reach
+    assert_eq!(grid_hits, single_cell_hits);
+
+    println!(
+        "Collisions of {} projectiles against {} ennemies, average of {} ticks",
+        BENCHMARK_PROJECTILES, BENCHMARK_ENNEMIES, BENCHMARK_ITERATIONS
+    );
+    println!("Brute force: {:?} per tick ({} hits)", single_cell_duration, single_cell_hits);
+    println!("Spatial grid: {:?} per tick ({} hits)", grid_duration, grid_hits);
+    println!(
+        "Speedup: x{:.1}",
+        single_cell_duration.as_secs_f64() / grid_duration.as_secs_f64().max(f64::EPSILON)
+    );
+}
@@ -0,0 +1,167 @@
+use crate::game_entity::health::Health;
+use crate::game_system::game_config::BossPhaseConfig;
+
+use bevy::ecs::component::Component;
+use std::f32::consts::PI;
+
+/// The fight of a boss, attached to the `Ennemy` of the boss. The attacks of the boss follow the phase of the fight
+#[derive(Component)]
+pub struct Boss {
+    /// Index of the current phase in the phases of the `BossConfig`
+    phase: usize,
+    time_until_burst: f32,
+    time_until_summon: f32,
+    /// Number of radial bursts fired, every other burst is turned by half the angle between two projectiles
+    bursts_fired: u32,
+}
+
+/// The attacks of a boss launched at a gameplay tick
+#[derive(Debug, Default, PartialEq)]
+pub struct BossAttacks {
+    /// Number of projectiles of the radial burst, 0 without burst
+    pub burst_projectiles: u32,
+    /// Angle of the first projectile of the radial burst, in radians
+    pub burst_angle: f32,
+    /// Number of minions to summon, 0 without summon
+    pub summoned_minions: u32,
+}
+
+impl Boss {
+    /// Returns a new Boss, in the first phase of its fight. Its first attacks wait for their interval
+    ///
+    /// # Arguments
+    ///
+    /// * `phases` - The phases of the fight
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///     # use street_of_zombies::game_entity::ennemies::boss::Boss;
+    ///     # use street_of_zombies::game_system::game_config::BossConfig;
+    ///     let boss = Boss::new(&BossConfig::default().phases);
+    ///     assert_eq!(boss.get_phase(), 0);
+    /// ```
+    pub fn new(phases: &[BossPhaseConfig]) -> Self {
+        let (time_until_burst, time_until_summon) = phases
+            .first()
+            .map_or((0., 0.), |phase| (phase.burst_interval, phase.summon_interval));
+
+        Boss {
+            phase: 0,
+            time_until_burst,
+            time_until_summon,
+            bursts_fired: 0,
+        }
+    }
+
+    /// Get the index of the current phase of the fight, from 0
+    pub fn get_phase(&self) -> usize {
+        self.phase
+    }
+
+    /// Move to the phase matching the health of the boss, then advance the timers of its attacks.
+    /// Returns the attacks to launch. A new phase launches its attacks at once
+    ///
+    /// # Arguments
+    ///
+    /// * `time_elapsed_since_last_update` - The game time elapsed since the last update, in seconds
+    /// * `health` - The health of the boss
+    /// * `phases` - The phases of the fight
+    ///
+    pub fn update(
+        &mut self,
+        time_elapsed_since_last_update: f32,
+        health: &Health,
+        phases: &[BossPhaseConfig],
+    ) -> BossAttacks {
+        // A fight never goes back to a previous phase
+        let phase_of_health = get_phase_of_health(health, phases);
+        if phase_of_health > self.phase {
+            self.phase = phase_of_health;
+            self.time_until_burst = 0.;
+            self.time_until_summon = 0.;
+        }
+
+        let mut attacks = BossAttacks::default();
+        let Some(phase) = phases.get(self.phase) else {
+            return attacks;
+        };
+
+        if phase.burst_projectiles > 0 {
+            self.time_until_burst -= time_elapsed_since_last_update;
+            if self.time_until_burst <= 0. {
+                self.time_until_burst = phase.burst_interval;
+                attacks.burst_projectiles = phase.burst_projectiles;
+                attacks.burst_angle = (self.bursts_fired % 2) as f32 * PI / phase.burst_projectiles as f32;
+                self.bursts_fired += 1;
+            }
+        }
+
+        if phase.summoned_minions > 0 {
+            self.time_until_summon -= time_elapsed_since_last_update;
+            if self.time_until_summon <= 0. {
+                self.time_until_summon = phase.summon_interval;
+                attacks.summoned_minions = phase.summoned_minions;
+            }
+        }
+
+        attacks
+    }
+}
+
+/// Get the last phase started by the health of the boss, the first phase without any started phase
+fn get_phase_of_health(health: &Health, phases: &[BossPhaseConfig]) -> usize {
+    phases
+        .iter()
+        .rposition(|phase| health.get_current() * 100 <= health.get_max() * phase.health_percent)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_system::game_config::BossConfig;
+
+    #[test]
+    fn phases_follow_the_health_of_the_boss() {
+        let phases = BossConfig::default().phases;
+        let mut boss = Boss::new(&phases);
+        let mut health = Health::new(100);
+
+        boss.update(0.1, &health, &phases);
+        assert_eq!(boss.get_phase(), 0);
+
+        health.take_damage(100 - phases[1].health_percent);
+        let attacks = boss.update(0.1, &health, &phases);
+        assert_eq!(boss.get_phase(), 1);
+        // The new phase attacks at once
+        assert_eq!(attacks.burst_projectiles, phases[1].burst_projectiles);
+        assert_eq!(attacks.summoned_minions, phases[1].summoned_minions);
+
+        health.take_damage(phases[1].health_percent - phases[2].health_percent);
+        boss.update(0.1, &health, &phases);
+        assert_eq!(boss.get_phase(), 2);
+
+        health.heal(100);
+        boss.update(0.1, &health, &phases);
+        assert_eq!(boss.get_phase(), 2);
+    }
+
+    #[test]
+    fn attacks_wait_for_their_interval() {
+        let phases = BossConfig::default().phases;
+        let mut boss = Boss::new(&phases);
+        let health = Health::new(100);
+
+        assert_eq!(boss.update(phases[0].burst_interval / 2., &health, &phases), BossAttacks::default());
+
+        let first_burst = boss.update(phases[0].burst_interval / 2., &health, &phases);
+        assert_eq!(first_burst.burst_projectiles, phases[0].burst_projectiles);
+        assert_eq!(first_burst.burst_angle, 0.);
+        // No summon in the first phase
+        assert_eq!(first_burst.summoned_minions, 0);
+
+        let second_burst = boss.update(phases[0].burst_interval, &health, &phases);
+        assert!(second_burst.burst_angle > 0.);
+    }
+}
@@ -1,27 +1,69 @@
+pub mod boss;
+
 use crate::game_entity::MoveableSprite;
 use crate::game_entity::MoveableSpriteTrait;
+use crate::game_system::game_config::EnnemyConfig;
 
-use crate::weapons::Pistol;
+use crate::weapons::FireResult;
+use crate::weapons::Firearm;
 use crate::weapons::Weapon;
 
 use bevy::prelude::*;
 use bevy::ecs::component::Component;
+use serde::{Deserialize, Serialize};
+
+/// Every archetype of ennemy, their characteristics are in the `EnnemyConfig`
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnnemyKind {
+    /// The common zombie, with a small gun
+    Walker,
+    /// Fast and fragile, melee only
+    Runner,
+    /// Slow, fires from far away
+    Spitter,
+    /// Slow and resistant, with a big hitbox
+    Tank,
+    /// Explodes when it dies
+    Exploder,
+    /// Huge, fought at some difficulty levels. It never spawns with the other ennemies
+    Boss,
+}
 
-// Default pistol weapon data
-static PROJECTILE_SPEED: f32 = 300.0;
-static AMO_IN_WEAPON: u32 = 3;
-static LIMIT_OF_FIRE: u32 = 500;
-static FIRE_RATE: f32 = 0.5;
-static INITIAL_HEALTH_POINTS: i32 = 3;
-static DEATH_POINT_COEF: u32 = 4;
-static DEFAULT_ENNEMY_HITBOX_SIZE: (f32, f32) = (40., 50.);
+/// Every archetype of the regular spawns, in the order of their spawn weights
+pub static ENNEMY_KINDS: [EnnemyKind; 5] = [
+    EnnemyKind::Walker,
+    EnnemyKind::Runner,
+    EnnemyKind::Spitter,
+    EnnemyKind::Tank,
+    EnnemyKind::Exploder,
+];
+
+/// The behaviour of an ennemy, chosen at each gameplay tick from the position of the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnnemyAiState {
+    /// The player is out of sight: the ennemy walks around its spawn position
+    Wander,
+    /// The ennemy runs toward the player
+    Chase,
+    /// The ennemy stops and fires at the player
+    Attack,
+    /// The player is too close: the ennemy runs away
+    Flee,
+}
 
 struct EnnemyInternalData {
-    health: i32,
-    current_weapon: Box<dyn Weapon + Send + Sync>,
-    tick_elapsed: f32,
-    cooldown_tick: f32,
+    kind: EnnemyKind,
+    /// Distance from the player under which the ennemy fires at the player
+    attack_range: f32,
+    /// Distance from the player under which the ennemy runs away, 0 when it never flees
+    flee_distance: f32,
+    /// The position where the ennemy was created
+    initial_position: Vec2,
+    /// None for a melee only ennemy
+    current_weapon: Option<Box<dyn Weapon + Send + Sync>>,
     points_per_hits: u32,
+    death_point_coef: u32,
+    contact_damage: u32,
 }
 
 /// An ennemy entity - An Ennemy object contains all the data necessary for a single ennemy
@@ -46,111 +88,166 @@ impl Ennemy {
     ///
     /// # Arguments
     ///
-    /// * `speed_to_set` - The speed of the ennemy
-    /// * `initial_post` - The initial position on the game area
-    /// * `fire_direction` - The default direction of fire
-    /// * `points` - The number of points valued by the ennemy
+    /// * `config` - The configuration of the ennemies (speed, points, weapon of each archetype). The health is a separate component
+    /// * `kind` - The archetype of the ennemy
+    /// * `direction_to_set` - The default direction of movement and fire
+    /// * `initial_pos` - The initial position on the game area
     /// # Examples
     ///
     /// ```
-    ///     let ennemy = let ennemy = Ennemy::new(500.0, (5., 10.), (15., 20.), 50);
+    ///     # use bevy::prelude::*;
+    ///     # use street_of_zombies::game_entity::ennemies::{Ennemy, EnnemyKind};
+    ///     # use street_of_zombies::game_system::game_config::EnnemyConfig;
+    ///     let ennemy = Ennemy::new(&EnnemyConfig::default(), EnnemyKind::Walker, Vec2::new(5., 10.), Vec2::new(15., 20.));
     /// ```
-    pub fn new(
-        speed_to_set: f32,
-        direction_to_set: (f32, f32),
-        initial_pos: (f32, f32),
-        points: u32,
-    ) -> Self {
+    pub fn new(config: &EnnemyConfig, kind: EnnemyKind, direction_to_set: Vec2, initial_pos: Vec2) -> Self {
+        let archetype = config.get(kind);
+        let current_weapon = archetype
+            .weapon
+            .as_ref()
+            .map(|weapon| Box::new(Firearm::new(weapon)) as Box<dyn Weapon + Send + Sync>);
+
         Ennemy {
             internal_data: EnnemyInternalData {
-                health: INITIAL_HEALTH_POINTS,
-                current_weapon: Box::new(Pistol::new(
-                    PROJECTILE_SPEED,
-                    FIRE_RATE,
-                    AMO_IN_WEAPON,
-                    LIMIT_OF_FIRE,
-                )),
-                tick_elapsed: 0.,
-                cooldown_tick: 2.5,
-                points_per_hits: points,
+                kind,
+                attack_range: archetype.attack_range,
+                flee_distance: archetype.flee_distance,
+                initial_position: initial_pos,
+                current_weapon,
+                points_per_hits: archetype.points_per_hit,
+                death_point_coef: archetype.death_point_coef,
+                contact_damage: archetype.contact_damage,
             },
-            sprite_data: MoveableSprite::new(
-                speed_to_set,
-                direction_to_set,
-                initial_pos,
-                DEFAULT_ENNEMY_HITBOX_SIZE,
-            ),
+            sprite_data: MoveableSprite::new(archetype.speed, direction_to_set, archetype.hitbox_size),
         }
     }
 
-    /// Reduce the ennemy health by one
+    /// Get the behaviour of the ennemy from its distance to the player
     ///
-    /// # Examples
+    /// # Arguments
     ///
-    /// ```
-    ///     let ennemy = let ennemy = Ennemy::new(500.0, (5., 10.), (15., 20.), (25., 30.), 50);
-    ///     ennemy.reduce_health();
-    /// ```
-    pub fn reduce_health(&mut self) {
-        self.internal_data.health -= 1;
-    }
-
-    /// Return true if the ennemy health is equal to 0
+    /// * `distance_to_player` - The distance between the ennemy and the player, None without player
+    /// * `detection_radius` - The distance under which the ennemy sees the player
     ///
     /// # Examples
     ///
     /// ```
-    ///     let ennemy = let ennemy = Ennemy::new(500.0, (5., 10.), (15., 20.), (25., 30.), 50);
-    ///     ennemy.reduce_health();
-    ///     if ennemy.is_dead() {
-    ///        println!("I am dead!");
-    ///     }
+    ///     # use bevy::prelude::*;
+    ///     # use street_of_zombies::game_entity::ennemies::{Ennemy, EnnemyAiState, EnnemyKind};
+    ///     # use street_of_zombies::game_system::game_config::EnnemyConfig;
+    ///     let ennemy = Ennemy::new(&EnnemyConfig::default(), EnnemyKind::Walker, Vec2::new(5., 10.), Vec2::new(15., 20.));
+    ///     assert_eq!(ennemy.get_ai_state(Some(1000.), 450.), EnnemyAiState::Wander);
     /// ```
-    pub fn is_dead(&self) -> bool {
-        self.internal_data.health < 1
+    pub fn get_ai_state(&self, distance_to_player: Option<f32>, detection_radius: f32) -> EnnemyAiState {
+        match distance_to_player {
+            Some(distance) if distance <= detection_radius => {
+                if distance < self.internal_data.flee_distance {
+                    EnnemyAiState::Flee
+                } else if self.internal_data.current_weapon.is_some() && distance <= self.internal_data.attack_range {
+                    EnnemyAiState::Attack
+                } else {
+                    EnnemyAiState::Chase
+                }
+            }
+            _ => EnnemyAiState::Wander,
+        }
     }
 
     /// The ennemy try to launch a projectile. If the weapon is charged (amo > 0), a projectile is launched.
-    /// During an attack, the ennemy can "reload" its weapon if the "cooldown-tick" is passed
+    /// Once the magazine is empty, the ennemy reloads its weapon. Returns what happened when the trigger was pulled,
+    /// a melee only ennemy is never ready to fire
     ///
     /// # Arguments
     ///
     /// * `commands` - The bevy command
-    /// * `time` - The timer (used for reloading)
-    /// * `points` - The number of points valued by the ennemy
-    /// ```
+    /// * `time` - The timer (used for the fire rate)
+    /// * `position` - The current position of the ennemy, where the projectiles are launched
+    /// * `direction` - The direction of fire
+    ///
     pub fn launch_attack(
         &mut self,
         commands: &mut Commands,
         time: &Res<Time>,
-    ) {
-        self.internal_data.current_weapon.fire_global(
+        position: Vec2,
+        direction: Vec2,
+    ) -> FireResult {
+        let Some(current_weapon) = self.internal_data.current_weapon.as_mut() else {
+            return FireResult::NotReady;
+        };
+
+        current_weapon.fire_global(
             commands,
             time,
-            self.sprite_data.get_direction(),
-            self.sprite_data.get_position(),
+            direction,
+            position,
             true,
             // Add a dummy argument if required by the trait
-        );
-
-        self.internal_data.tick_elapsed += time.delta_seconds();
+        )
+    }
 
-        if self.internal_data.tick_elapsed > self.internal_data.cooldown_tick {
-            self.internal_data.current_weapon.reload();
-            self.internal_data.tick_elapsed = 0.;
+    /// Advance the reload of the weapon, whatever the ennemy is doing
+    ///
+    /// # Arguments
+    ///
+    /// * `time_elapsed_since_last_update` - The game time elapsed since the last update, in seconds
+    ///
+    pub fn update_weapon(&mut self, time_elapsed_since_last_update: f32) {
+        if let Some(current_weapon) = self.internal_data.current_weapon.as_mut() {
+            current_weapon.update_reload(time_elapsed_since_last_update);
         }
     }
 
+    /// Get the speed of the projectiles of the ennemy, None for a melee only ennemy
+    pub fn get_projectile_speed(&self) -> Option<f32> {
+        self.internal_data
+            .current_weapon
+            .as_ref()
+            .map(|current_weapon| current_weapon.get_projectile_speed())
+    }
+
+    /// Get the archetype of the ennemy
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///     # use bevy::prelude::*;
+    ///     # use street_of_zombies::game_entity::ennemies::{Ennemy, EnnemyKind};
+    ///     # use street_of_zombies::game_system::game_config::EnnemyConfig;
+    ///     let ennemy = Ennemy::new(&EnnemyConfig::default(), EnnemyKind::Tank, Vec2::new(5., 10.), Vec2::new(15., 20.));
+    ///     assert_eq!(ennemy.get_kind(), EnnemyKind::Tank);
+    /// ```
+    pub fn get_kind(&self) -> EnnemyKind {
+        self.internal_data.kind
+    }
+
     /// Get the initial position where the ennemy was created
     ///
     /// # Examples
     ///
     /// ```
-    ///     let ennemy = Ennemy::new(500.0, (5., 10.), (15., 20.), (25., 30.), 50);
-    ///     assert_eq!(ennemy.get_initial_position(), (15., 20.));
+    ///     # use bevy::prelude::*;
+    ///     # use street_of_zombies::game_entity::ennemies::{Ennemy, EnnemyKind};
+    ///     # use street_of_zombies::game_system::game_config::EnnemyConfig;
+    ///     let ennemy = Ennemy::new(&EnnemyConfig::default(), EnnemyKind::Walker, Vec2::new(5., 10.), Vec2::new(15., 20.));
+    ///     assert_eq!(ennemy.get_initial_position(), Vec2::new(15., 20.));
+    /// ```
+    pub fn get_initial_position(&self) -> Vec2 {
+        self.internal_data.initial_position
+    }
+
+    /// Get the health removed from the player when the ennemy touches it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///     # use bevy::prelude::*;
+    ///     # use street_of_zombies::game_entity::ennemies::{Ennemy, EnnemyKind};
+    ///     # use street_of_zombies::game_system::game_config::EnnemyConfig;
+    ///     let ennemy = Ennemy::new(&EnnemyConfig::default(), EnnemyKind::Walker, Vec2::new(5., 10.), Vec2::new(15., 20.));
+    ///     assert_eq!(ennemy.get_contact_damage(), 1);
     /// ```
-    pub fn get_initial_position(&self) -> (f32, f32) {
-        self.sprite_data.internal_data.initial_position
+    pub fn get_contact_damage(&self) -> u32 {
+        self.internal_data.contact_damage
     }
 
     /// Get the value (in point for the score) of the ennemy per hits
@@ -158,7 +255,10 @@ impl Ennemy {
     /// # Examples
     ///
     /// ```
-    ///     let ennemy = Ennemy::new(500.0, (5., 10.), (15., 20.), (25., 30.), 50);
+    ///     # use bevy::prelude::*;
+    ///     # use street_of_zombies::game_entity::ennemies::{Ennemy, EnnemyKind};
+    ///     # use street_of_zombies::game_system::game_config::EnnemyConfig;
+    ///     let ennemy = Ennemy::new(&EnnemyConfig::default(), EnnemyKind::Walker, Vec2::new(5., 10.), Vec2::new(15., 20.));
     ///     assert_eq!(ennemy.get_point_value_per_hits(), 50);
     /// ```
     pub fn get_point_value_per_hits(&self) -> u32 {
@@ -170,11 +270,14 @@ impl Ennemy {
     /// # Examples
     ///
     /// ```
-    ///     let ennemy = Ennemy::new(500.0, (5., 10.), (15., 20.), (25., 30.), 50);
-    ///     assert_eq!(ennemy.get_point_value_on_death(), 50*DEATH_POINT_COEF);
+    ///     # use bevy::prelude::*;
+    ///     # use street_of_zombies::game_entity::ennemies::{Ennemy, EnnemyKind};
+    ///     # use street_of_zombies::game_system::game_config::EnnemyConfig;
+    ///     let ennemy = Ennemy::new(&EnnemyConfig::default(), EnnemyKind::Walker, Vec2::new(5., 10.), Vec2::new(15., 20.));
+    ///     assert_eq!(ennemy.get_point_value_on_death(), 50 * 4);
     /// ```
     pub fn get_point_value_on_death(&self) -> u32 {
-        self.internal_data.points_per_hits * DEATH_POINT_COEF
+        self.internal_data.points_per_hits * self.internal_data.death_point_coef
     }
 }
 
@@ -184,49 +287,59 @@ mod tests {
 
     #[test]
     fn ennemy_get_initial_position() {
-        let ennemy = Ennemy::new(500.0, (5., 10.), (15., 20.), 50);
+        let ennemy = Ennemy::new(&EnnemyConfig::default(), EnnemyKind::Walker, Vec2::new(5., 10.), Vec2::new(15., 20.));
 
-        assert_eq!(ennemy.get_initial_position(), (15., 20.));
+        assert_eq!(ennemy.get_initial_position(), Vec2::new(15., 20.));
     }
 
     #[test]
     fn ennemy_get_value_per_hits() {
-        let ennemy = Ennemy::new(500.0, (5., 10.), (15., 20.), 50);
+        let ennemy = Ennemy::new(&EnnemyConfig::default(), EnnemyKind::Walker, Vec2::new(5., 10.), Vec2::new(15., 20.));
 
         assert_eq!(ennemy.get_point_value_per_hits(), 50);
     }
 
     #[test]
-    fn ennemy_get_value_on_death() {
-        let ennemy = Ennemy::new(500.0, (5., 10.), (15., 20.), 50);
+    fn melee_ennemy_never_fires() {
+        use bevy::ecs::system::RunSystemOnce;
 
-        assert_eq!(ennemy.get_point_value_on_death(), 50 * DEATH_POINT_COEF);
-    }
+        let mut world = World::new();
+        world.init_resource::<Time>();
 
-    #[test]
-    fn ennemy_set_position_without_alter_default() {
-        let mut ennemy = Ennemy::new(500.0, (5., 10.), (15., 20.), 50);
-        ennemy.sprite_data.set_new_position((60., 40.));
-        assert_eq!(ennemy.get_initial_position(), (15., 20.));
+        world.run_system_once(|mut commands: Commands, time: Res<Time>| {
+            let mut runner = Ennemy::new(&EnnemyConfig::default(), EnnemyKind::Runner, Vec2::new(0., 1.), Vec2::ZERO);
+
+            assert_eq!(
+                runner.launch_attack(&mut commands, &time, Vec2::ZERO, Vec2::new(0., 1.)),
+                FireResult::NotReady
+            );
+        });
+        assert!(world.query::<&crate::game_entity::projectiles::Projectile>().iter(&world).next().is_none());
     }
 
     #[test]
-    fn ennemy_reduce_health() {
-        let mut ennemy = Ennemy::new(500.0, (5., 10.), (15., 20.), 50);
-
-        ennemy.reduce_health();
+    fn ai_state_follows_the_distance_to_the_player() {
+        let config = EnnemyConfig::default();
+        let walker = Ennemy::new(&config, EnnemyKind::Walker, Vec2::new(0., 1.), Vec2::ZERO);
+        let spitter = Ennemy::new(&config, EnnemyKind::Spitter, Vec2::new(0., 1.), Vec2::ZERO);
+        let runner = Ennemy::new(&config, EnnemyKind::Runner, Vec2::new(0., 1.), Vec2::ZERO);
 
-        assert_eq!(ennemy.is_dead(), INITIAL_HEALTH_POINTS == 1);
+        assert_eq!(walker.get_ai_state(None, 450.), EnnemyAiState::Wander);
+        assert_eq!(walker.get_ai_state(Some(500.), 450.), EnnemyAiState::Wander);
+        assert_eq!(walker.get_ai_state(Some(400.), 450.), EnnemyAiState::Chase);
+        assert_eq!(walker.get_ai_state(Some(config.walker.attack_range), 450.), EnnemyAiState::Attack);
+        assert_eq!(spitter.get_ai_state(Some(config.spitter.flee_distance - 1.), 450.), EnnemyAiState::Flee);
+        // Melee only, the runner always chases the player it sees
+        assert_eq!(runner.get_ai_state(Some(10.), 450.), EnnemyAiState::Chase);
     }
 
     #[test]
-    pub fn ennemy_death_test() {
-        let mut ennemy = Ennemy::new(500.0, (5., 10.), (15., 20.), 50);
-
-        for _ in 0..INITIAL_HEALTH_POINTS {
-            ennemy.reduce_health();
-        }
+    fn ennemy_get_value_on_death() {
+        let ennemy = Ennemy::new(&EnnemyConfig::default(), EnnemyKind::Walker, Vec2::new(5., 10.), Vec2::new(15., 20.));
 
-        assert_eq!(ennemy.is_dead(), true);
+        assert_eq!(
+            ennemy.get_point_value_on_death(),
+            50 * EnnemyConfig::default().walker.death_point_coef
+        );
     }
 }
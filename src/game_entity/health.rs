@@ -0,0 +1,181 @@
+use bevy::prelude::*;
+
+/// The health of a character (the player or an ennemy)
+#[derive(Component)]
+pub struct Health {
+    current: u32,
+    max: u32,
+    /// Health given back per second, 0 without regeneration
+    regen_per_second: f32,
+    /// Damage absorbed by each hit
+    armor: u32,
+    /// Regenerated health not given back yet, a point is given back once it reaches 1
+    regen_progress: f32,
+}
+
+impl Health {
+    /// Returns a new Health, full
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - The maximum (and initial) health
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///     # use street_of_zombies::game_entity::health::Health;
+    ///     let health = Health::new(5).with_regen(0.1).with_armor(1);
+    /// ```
+    pub fn new(max: u32) -> Self {
+        Health {
+            current: max,
+            max,
+            regen_per_second: 0.0,
+            armor: 0,
+            regen_progress: 0.0,
+        }
+    }
+
+    /// Give back some health over time
+    ///
+    /// # Arguments
+    ///
+    /// * `regen_per_second` - The health given back per second
+    ///
+    pub fn with_regen(mut self, regen_per_second: f32) -> Self {
+        self.regen_per_second = regen_per_second.max(0.0);
+        self
+    }
+
+    /// Absorb a part of the damage of each hit. A hit always removes at least 1 health
+    ///
+    /// # Arguments
+    ///
+    /// * `armor` - The damage absorbed by each hit
+    ///
+    pub fn with_armor(mut self, armor: u32) -> Self {
+        self.armor = armor;
+        self
+    }
+
+    /// Apply the damage of a hit, reduced by the armor. Returns the health removed
+    ///
+    /// # Arguments
+    ///
+    /// * `damage` - The damage of the hit
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///     # use street_of_zombies::game_entity::health::Health;
+    ///     let mut health = Health::new(5).with_armor(1);
+    ///     assert_eq!(health.take_damage(3), 2);
+    ///     assert_eq!(health.get_current(), 3);
+    /// ```
+    pub fn take_damage(&mut self, damage: u32) -> u32 {
+        if damage == 0 {
+            return 0;
+        }
+
+        let health_removed = damage.saturating_sub(self.armor).max(1).min(self.current);
+        self.current -= health_removed;
+        health_removed
+    }
+
+    /// Give back some health, up to the maximum health. The dead stay dead
+    ///
+    /// # Arguments
+    ///
+    /// * `health_to_add` - The health given back
+    ///
+    pub fn heal(&mut self, health_to_add: u32) {
+        if self.is_dead() {
+            return;
+        }
+
+        self.current = (self.current + health_to_add).min(self.max);
+    }
+
+    /// Advance the regeneration
+    ///
+    /// # Arguments
+    ///
+    /// * `time_elapsed_since_last_update` - The game time elapsed since the last update, in seconds
+    ///
+    pub fn regenerate(&mut self, time_elapsed_since_last_update: f32) {
+        if self.is_dead() || self.current >= self.max {
+            self.regen_progress = 0.0;
+            return;
+        }
+
+        self.regen_progress += self.regen_per_second * time_elapsed_since_last_update;
+        let regenerated_health = self.regen_progress.floor();
+        self.regen_progress -= regenerated_health;
+        self.heal(regenerated_health as u32);
+    }
+
+    /// Get the current health
+    pub fn get_current(&self) -> u32 {
+        self.current
+    }
+
+    /// Get the maximum health
+    pub fn get_max(&self) -> u32 {
+        self.max
+    }
+
+    /// Return true if the health is equal to 0
+    pub fn is_dead(&self) -> bool {
+        self.current == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn damage_cannot_go_below_zero() {
+        let mut health = Health::new(3);
+
+        assert_eq!(health.take_damage(5000), 3);
+        assert_eq!(health.get_current(), 0);
+        assert!(health.is_dead());
+    }
+
+    #[test]
+    fn armor_absorbs_a_part_of_the_damage() {
+        let mut health = Health::new(10).with_armor(2);
+
+        assert_eq!(health.take_damage(5), 3);
+        // A weak hit still hurts
+        assert_eq!(health.take_damage(1), 1);
+        assert_eq!(health.get_current(), 6);
+    }
+
+    #[test]
+    fn heal_up_to_the_maximum_health() {
+        let mut health = Health::new(5);
+        health.take_damage(2);
+
+        health.heal(1);
+        assert_eq!(health.get_current(), 4);
+        health.heal(5);
+        assert_eq!(health.get_current(), health.get_max());
+    }
+
+    #[test]
+    fn regeneration_gives_back_whole_points() {
+        let mut health = Health::new(5).with_regen(0.5);
+        health.take_damage(2);
+
+        health.regenerate(1.0);
+        assert_eq!(health.get_current(), 3);
+        health.regenerate(1.0);
+        assert_eq!(health.get_current(), 4);
+
+        health.take_damage(4);
+        health.regenerate(10.0);
+        assert!(health.is_dead());
+    }
+}
@@ -1,23 +1,26 @@
 pub mod ennemies;
+pub mod health;
+pub mod pickups;
 pub mod player;
 pub mod projectiles;
 
-use crate::game_system::GAME_AREA_LIMIT_X;
-use crate::game_system::GAME_AREA_LIMIT_Y;
+use crate::game_system::game_config::GameAreaConfig;
 
 use bevy::prelude::*;
 
+/// The movement data of an entity. Its position is the `Transform` of the entity, the only source of truth
 pub struct MoveableSprite {
     internal_data: MoveableSpriteData,
 }
 
 struct MoveableSpriteData {
     speed: f32,
-    direction: (f32, f32),
-    initial_position: (f32, f32),
-    current_position: (f32, f32),
-    previous_position: (f32, f32),
-    hitbox_size: (f32, f32),
+    direction: Vec2,
+    hitbox_size: Vec2,
+    /// True if the last movement changed the position of the sprite
+    is_moved: bool,
+    /// The position before the last movement, None if the sprite never moved
+    previous_position: Option<Vec2>,
 }
 
 pub trait MoveableSpriteTrait {
@@ -27,20 +30,14 @@ pub trait MoveableSpriteTrait {
 
 /// A sprite which is able to move
 impl MoveableSprite {
-    fn new(
-        speed_to_set: f32,
-        direction_to_set: (f32, f32),
-        current_position_to_set: (f32, f32),
-        hitbox_size: (f32, f32),
-    ) -> Self {
+    fn new(speed_to_set: f32, direction_to_set: Vec2, hitbox_size: Vec2) -> Self {
         MoveableSprite {
             internal_data: MoveableSpriteData {
                 speed: speed_to_set,
                 direction: direction_to_set,
-                initial_position: current_position_to_set,
-                current_position: current_position_to_set,
-                previous_position: current_position_to_set,
-                hitbox_size: hitbox_size,
+                hitbox_size,
+                is_moved: false,
+                previous_position: None,
             },
         }
     }
@@ -50,6 +47,11 @@ impl MoveableSprite {
     /// # Examples
     ///
     /// ```
+    /// # use bevy::prelude::*;
+    /// # use street_of_zombies::game_entity::MoveableSpriteTrait;
+    /// # use street_of_zombies::game_entity::projectiles::Projectile;
+    /// # let projectile = Projectile::new(500.0, Vec2::new(1., 0.), Vec2::new(15., 20.), 500, false);
+    /// # let my_moveable_sprit = projectile.get_moveable_interface();
     /// let current_speed : f32 = my_moveable_sprit.get_speed();
     /// ```
     pub fn get_speed(&self) -> f32 {
@@ -61,9 +63,14 @@ impl MoveableSprite {
     /// # Examples
     ///
     /// ```
-    /// let current_speed : f32 = my_moveable_sprit.get_direction();
+    /// # use bevy::prelude::*;
+    /// # use street_of_zombies::game_entity::MoveableSpriteTrait;
+    /// # use street_of_zombies::game_entity::projectiles::Projectile;
+    /// # let projectile = Projectile::new(500.0, Vec2::new(1., 0.), Vec2::new(15., 20.), 500, false);
+    /// # let my_moveable_sprit = projectile.get_moveable_interface();
+    /// let current_direction : Vec2 = my_moveable_sprit.get_direction();
     /// ```
-    pub fn get_direction(&self) -> (f32, f32) {
+    pub fn get_direction(&self) -> Vec2 {
         self.internal_data.direction
     }
 
@@ -71,55 +78,83 @@ impl MoveableSprite {
     ///
     /// # Arguments
     ///
-    /// * `direction` - The new direction of your moveable sprite, set as (x, y)
+    /// * `direction` - The new direction of your moveable sprite
     ///
-    pub fn set_new_direction(&mut self, direction: (f32, f32)) {
+    pub fn set_new_direction(&mut self, direction: Vec2) {
         self.internal_data.direction = direction;
     }
 
-    /// Get the current position of a moveable sprite in the game area
-    pub fn get_position(&self) -> (f32, f32) {
-        self.internal_data.current_position
-    }
-
-    /// Set the new position of a moveable sprite
-    ///
-    /// # Arguments
+    /// Get the hitbox size of the MoveableCharacter
     ///
-    /// * `position` - The new position of your moveable sprite, set as (x, y)
+    /// # Examples
     ///
-    pub fn set_new_position(&mut self, position: (f32, f32)) {
-        self.internal_data.current_position = position;
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use street_of_zombies::game_entity::MoveableSpriteTrait;
+    /// # use street_of_zombies::game_entity::projectiles::Projectile;
+    /// # let projectile = Projectile::new(500.0, Vec2::new(1., 0.), Vec2::new(15., 20.), 500, false);
+    /// # let my_moveable_sprit = projectile.get_moveable_interface();
+    /// let hitbox : Vec2 = my_moveable_sprit.get_hitbox_size();
+    /// ```
+    pub fn get_hitbox_size(&self) -> Vec2 {
+        self.internal_data.hitbox_size
     }
 
-    /// Get the hitbox size of the MoveableCharacter in format (x, y)
+    /// Check if the last movement changed the position of the sprite.
     ///
     /// # Examples
     ///
     /// ```
-    /// let hitbox : (f32, f32) = my_moveable_sprit.get_hitbox_size();
+    /// # use bevy::prelude::*;
+    /// # use street_of_zombies::game_entity::MoveableSpriteTrait;
+    /// # use street_of_zombies::game_entity::projectiles::Projectile;
+    /// # use street_of_zombies::game_system::game_config::GameAreaConfig;
+    /// # use bevy::ecs::system::RunSystemOnce;
+    /// # use std::time::Duration;
+    /// # let mut world = World::new();
+    /// # let mut one_second = Time::<()>::default();
+    /// # one_second.advance_by(Duration::from_secs(1));
+    /// # world.insert_resource(one_second);
+    /// # world.run_system_once(|time: Res<Time>| {
+    /// # let mut projectile = Projectile::new(500.0, Vec2::new(1., 0.), Vec2::new(15., 20.), 500, false);
+    /// # let moveable_sprite = projectile.get_moveable_interface_mut();
+    /// # let game_area = GameAreaConfig::default();
+    /// let mut transform = Transform::default();
+    /// moveable_sprite.move_sprite(&time, Vec2::new(1., 0.), &mut transform, &game_area);
+    /// assert_eq!(moveable_sprite.is_sprite_moved(), true);
+    /// # });
     /// ```
-    pub fn get_hitbox_size(&self) -> (f32, f32) {
-        self.internal_data.hitbox_size
+    pub fn is_sprite_moved(&self) -> bool {
+        self.internal_data.is_moved
     }
 
-    /// Check if the sprite moved between each calls.
+    /// Get the position of the sprite before its last movement, None if it never moved.
+    /// The segment between this position and the current one is the path of the last movement
     ///
     /// # Examples
     ///
     /// ```
-    /// let mut moveable_sprite = MoveableSprite::new(500.0, (5., 10.), (15., 20.), (10., 10.));
-    ///
-    /// moveable_sprite.set_new_position((20., 50.0));
-    /// assert_eq!(moveable_sprite.is_sprite_moved_after_last_call(), true);
+    /// # use bevy::prelude::*;
+    /// # use street_of_zombies::game_entity::MoveableSpriteTrait;
+    /// # use street_of_zombies::game_entity::projectiles::Projectile;
+    /// # use street_of_zombies::game_system::game_config::GameAreaConfig;
+    /// # use bevy::ecs::system::RunSystemOnce;
+    /// # use std::time::Duration;
+    /// # let mut world = World::new();
+    /// # let mut one_second = Time::<()>::default();
+    /// # one_second.advance_by(Duration::from_secs(1));
+    /// # world.insert_resource(one_second);
+    /// # world.run_system_once(|time: Res<Time>| {
+    /// # let mut projectile = Projectile::new(500.0, Vec2::new(1., 0.), Vec2::new(15., 20.), 500, false);
+    /// # let moveable_sprite = projectile.get_moveable_interface_mut();
+    /// # let game_area = GameAreaConfig::default();
+    /// let mut transform = Transform::from_xyz(15., 20., 0.);
+    /// moveable_sprite.move_sprite(&time, Vec2::new(1., 0.), &mut transform, &game_area);
+    /// assert_eq!(moveable_sprite.get_previous_position(), Some(Vec2::new(15., 20.)));
+    /// # });
     /// ```
-    pub fn is_sprite_moved_after_last_call(&mut self) -> bool {
-        if self.internal_data.previous_position != self.internal_data.current_position {
-            self.internal_data.previous_position = self.internal_data.current_position;
-            return true;
-        }
-
-        return false;
+    pub fn get_previous_position(&self) -> Option<Vec2> {
+        self.internal_data.previous_position
     }
 
     /// Move the sprite to a new position.
@@ -128,122 +163,245 @@ impl MoveableSprite {
     ///
     /// * `time` - The timer provided by Bevy engine.
     /// * `direction` - The movement direction.
-    /// * `translated_movement` - The "translated movement" returned to bevy engine.
+    /// * `transform` - The transform of the entity, its translation is the position of the sprite.
+    /// * `game_area` - The limits of the game area.
     ///
     pub fn move_sprite(
         &mut self,
         time: &Res<Time>,
-        direction: &(f32, f32),
-        translated_movement: &mut bevy::prelude::Vec3,
+        direction: Vec2,
+        transform: &mut Transform,
+        game_area: &GameAreaConfig,
     ) {
-        // move the sprite
-        translated_movement.x += time.delta_seconds() * direction.0 * &self.get_speed();
-        translated_movement.y += time.delta_seconds() * direction.1 * &self.get_speed();
+        let previous_position = get_position(transform);
+        let next_position = position_to_game_area_limit(
+            previous_position + time.delta_seconds() * direction * self.get_speed(),
+            game_area,
+        );
+
+        transform.translation = next_position.extend(transform.translation.z);
+        self.internal_data.is_moved = next_position != previous_position;
+        self.internal_data.previous_position = Some(previous_position);
+        self.set_new_direction(direction);
+    }
+
+    /// The sprite does not move during this update: its last movement is empty, its direction is kept
+    ///
+    /// # Arguments
+    ///
+    /// * `transform` - The transform of the entity, its translation is the position of the sprite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use street_of_zombies::game_entity::MoveableSpriteTrait;
+    /// # use street_of_zombies::game_entity::projectiles::Projectile;
+    /// # let mut projectile = Projectile::new(500.0, Vec2::new(1., 0.), Vec2::new(15., 20.), 500, false);
+    /// # let moveable_sprite = projectile.get_moveable_interface_mut();
+    /// moveable_sprite.stop(&Transform::from_xyz(15., 20., 0.));
+    /// assert_eq!(moveable_sprite.is_sprite_moved(), false);
+    /// ```
+    pub fn stop(&mut self, transform: &Transform) {
+        self.internal_data.is_moved = false;
+        self.internal_data.previous_position = Some(get_position(transform));
+    }
+
+    /// Get the speed of the last movement of the sprite, in pixels per second
+    ///
+    /// # Arguments
+    ///
+    /// * `transform` - The transform of the entity, its translation is the position of the sprite.
+    /// * `time_elapsed_since_last_update` - The game time elapsed since the last update, in seconds
+    ///
+    pub fn get_velocity(&self, transform: &Transform, time_elapsed_since_last_update: f32) -> Vec2 {
+        let current_position = get_position(transform);
+        let previous_position = self.get_previous_position().unwrap_or(current_position);
+
+        if time_elapsed_since_last_update <= 0.0 {
+            return Vec2::ZERO;
+        }
 
-        position_to_game_area_limit(translated_movement);
+        (current_position - previous_position) / time_elapsed_since_last_update
+    }
 
-        self.set_new_position((translated_movement.x, translated_movement.y));
-        self.set_new_direction(*direction);
+    /// Push the sprite by an offset, without changing its direction. The sprite stays in the game area
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The movement of the push.
+    /// * `transform` - The transform of the entity, its translation is the position of the sprite.
+    /// * `game_area` - The limits of the game area.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use street_of_zombies::game_entity::MoveableSpriteTrait;
+    /// # use street_of_zombies::game_entity::projectiles::Projectile;
+    /// # use street_of_zombies::game_system::game_config::GameAreaConfig;
+    /// # use street_of_zombies::game_entity::get_position;
+    /// # let projectile = Projectile::new(500.0, Vec2::new(1., 0.), Vec2::new(15., 20.), 500, false);
+    /// # let moveable_sprite = projectile.get_moveable_interface();
+    /// # let game_area = GameAreaConfig::default();
+    /// let mut transform = Transform::from_xyz(15., 20., 0.);
+    /// moveable_sprite.push_sprite(Vec2::new(0., -60.), &mut transform, &game_area);
+    /// assert_eq!(get_position(&transform), Vec2::new(15., -40.));
+    /// ```
+    pub fn push_sprite(&self, offset: Vec2, transform: &mut Transform, game_area: &GameAreaConfig) {
+        let next_position = position_to_game_area_limit(get_position(transform) + offset, game_area);
+
+        transform.translation = next_position.extend(transform.translation.z);
     }
 }
 
+/// Get the position of an entity in the game area, from its transform
+///
+/// # Examples
+///
+/// ```
+///     # use bevy::prelude::*;
+///     # use street_of_zombies::game_entity::get_position;
+///     assert_eq!(get_position(&Transform::from_xyz(15., 20., 0.)), Vec2::new(15., 20.));
+/// ```
+pub fn get_position(transform: &Transform) -> Vec2 {
+    transform.translation.truncate()
+}
+
 /// Force the position to the game area limit
 ///
 /// # Arguments
 ///
-/// * `translated_movement` - The "translated movement" returned to bevy engine.
+/// * `position` - The position to limit.
+/// * `game_area` - The limits of the game area.
 ///
-fn position_to_game_area_limit(translated_movement: &mut bevy::prelude::Vec3) {
-    translated_movement.x = translated_movement
-        .x
-        .min(GAME_AREA_LIMIT_X)
-        .max(-GAME_AREA_LIMIT_X);
-    translated_movement.y = translated_movement
-        .y
-        .min(GAME_AREA_LIMIT_Y)
-        .max(-GAME_AREA_LIMIT_Y);
+fn position_to_game_area_limit(position: Vec2, game_area: &GameAreaConfig) -> Vec2 {
+    let limit = Vec2::new(game_area.limit_x, game_area.limit_y);
+
+    position.clamp(-limit, limit)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+    use std::time::Duration;
+
+    fn new_moveable_sprite() -> MoveableSprite {
+        MoveableSprite::new(500.0, Vec2::new(5., 10.), Vec2::new(10., 10.))
+    }
+
+    /// A time which advanced by one second
+    fn one_second() -> Time {
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs(1));
+        time
+    }
 
     #[test]
     fn moveable_sprite_initial_speed() {
-        let moveable_sprite = MoveableSprite::new(500.0, (5., 10.), (15., 20.), (10., 10.));
+        let moveable_sprite = new_moveable_sprite();
 
         assert_eq!(moveable_sprite.get_speed(), 500.0);
     }
 
     #[test]
     fn moveable_sprite_initial_direction() {
-        let moveable_sprite = MoveableSprite::new(500.0, (5., 10.), (15., 20.), (10., 10.));
+        let moveable_sprite = new_moveable_sprite();
 
-        assert_eq!(moveable_sprite.get_direction(), (5., 10.));
+        assert_eq!(moveable_sprite.get_direction(), Vec2::new(5., 10.));
     }
 
     #[test]
-    fn moveable_sprite_initial_position() {
-        let moveable_sprite = MoveableSprite::new(500.0, (5., 10.), (15., 20.), (10., 10.));
+    fn moveable_sprite_set_direction() {
+        let mut moveable_sprite = new_moveable_sprite();
 
-        assert_eq!(moveable_sprite.get_position(), (15., 20.));
+        moveable_sprite.set_new_direction(Vec2::new(100., 45.));
+        assert_eq!(moveable_sprite.get_direction(), Vec2::new(100., 45.));
     }
 
     #[test]
-    fn moveable_sprite_set_direction() {
-        let mut moveable_sprite = MoveableSprite::new(500.0, (5., 10.), (15., 20.), (10., 10.));
+    fn projectile_get_hitbox_size() {
+        let moveable_sprite = new_moveable_sprite();
 
-        moveable_sprite.set_new_direction((100., 45.));
-        assert_eq!(moveable_sprite.get_direction(), (100., 45.));
+        let hitbox_size = moveable_sprite.get_hitbox_size();
+        assert_eq!(hitbox_size, Vec2::new(10., 10.));
     }
 
     #[test]
-    fn moveable_sprite_set_position() {
-        let mut moveable_sprite = MoveableSprite::new(500.0, (5., 10.), (15., 20.), (10., 10.));
-
-        moveable_sprite.set_new_position((60., 40.));
-        assert_eq!(moveable_sprite.get_position(), (60., 40.));
+    fn sprite_moves_its_transform() {
+        let mut world = World::new();
+        world.insert_resource(one_second());
+        let mut moveable_sprite = MoveableSprite::new(100.0, Vec2::ZERO, Vec2::new(10., 10.));
+        let mut transform = Transform::from_xyz(15., 20., 3.);
+
+        world.run_system_once(move |time: Res<Time>| {
+            moveable_sprite.move_sprite(&time, Vec2::new(0.5, -1.), &mut transform, &GameAreaConfig::default());
+
+            assert!(moveable_sprite.is_sprite_moved());
+            assert_eq!(moveable_sprite.get_direction(), Vec2::new(0.5, -1.));
+            assert_eq!(moveable_sprite.get_previous_position(), Some(Vec2::new(15., 20.)));
+            // The depth of the sprite is kept
+            assert_eq!(transform.translation, Vec3::new(65., -80., 3.));
+        });
     }
 
     #[test]
-    fn projectile_get_hitbox_size() {
-        let moveable_sprite = MoveableSprite::new(500.0, (5., 10.), (15., 20.), (10., 10.));
-
-        let hitbox_size = moveable_sprite.get_hitbox_size();
-        assert_eq!(hitbox_size, (10., 10.));
+    fn stopped_sprite_has_no_velocity() {
+        let mut world = World::new();
+        world.insert_resource(one_second());
+        let mut moveable_sprite = MoveableSprite::new(100.0, Vec2::ZERO, Vec2::new(10., 10.));
+        let mut transform = Transform::from_xyz(15., 20., 0.);
+
+        world.run_system_once(move |time: Res<Time>| {
+            moveable_sprite.move_sprite(&time, Vec2::new(1., 0.), &mut transform, &GameAreaConfig::default());
+            assert_eq!(moveable_sprite.get_velocity(&transform, time.delta_seconds()), Vec2::new(100., 0.));
+
+            moveable_sprite.stop(&transform);
+            assert!(!moveable_sprite.is_sprite_moved());
+            assert_eq!(moveable_sprite.get_velocity(&transform, time.delta_seconds()), Vec2::ZERO);
+            assert_eq!(moveable_sprite.get_direction(), Vec2::new(1., 0.));
+        });
     }
 
     #[test]
-    fn sprite_moved_test() {
-        let mut moveable_sprite = MoveableSprite::new(500.0, (5., 10.), (15., 20.), (10., 10.));
+    fn sprite_not_moved_test() {
+        let moveable_sprite = new_moveable_sprite();
 
-        moveable_sprite.set_new_position((20., 50.0));
-        assert_eq!(moveable_sprite.is_sprite_moved_after_last_call(), true);
+        assert!(!moveable_sprite.is_sprite_moved());
+        assert_eq!(moveable_sprite.get_previous_position(), None);
     }
 
     #[test]
-    fn sprite_not_moved_test() {
-        let mut moveable_sprite = MoveableSprite::new(500.0, (5., 10.), (15., 20.), (10., 10.));
-
-        assert_eq!(moveable_sprite.is_sprite_moved_after_last_call(), false);
+    fn sprite_blocked_by_the_game_limit_does_not_move() {
+        let mut world = World::new();
+        world.insert_resource(one_second());
+        let game_area = GameAreaConfig::default();
+        let mut moveable_sprite = MoveableSprite::new(100.0, Vec2::ZERO, Vec2::new(10., 10.));
+        let mut transform = Transform::from_xyz(game_area.limit_x, 0., 0.);
+
+        world.run_system_once(move |time: Res<Time>| {
+            moveable_sprite.move_sprite(&time, Vec2::new(1., 0.), &mut transform, &game_area);
+
+            assert!(!moveable_sprite.is_sprite_moved());
+        });
     }
 
     #[test]
     fn inside_game_limit_test() {
-        let mut vect_in_game_are = Vec3::new(0.0, 0.0, 0.0);
-        position_to_game_area_limit(&mut vect_in_game_are);
+        let position = position_to_game_area_limit(Vec2::ZERO, &GameAreaConfig::default());
 
-        assert_eq!(vect_in_game_are.x, 0.0);
-        assert_eq!(vect_in_game_are.y, 0.0);
+        assert_eq!(position, Vec2::ZERO);
     }
 
     #[test]
     fn outside_game_limit_test() {
-        let mut vect_in_game_are =
-            Vec3::new(GAME_AREA_LIMIT_X + 50.0, GAME_AREA_LIMIT_Y + 50.0, 0.0);
-        position_to_game_area_limit(&mut vect_in_game_are);
+        let game_area = GameAreaConfig::default();
+        let position = position_to_game_area_limit(
+            Vec2::new(game_area.limit_x + 50.0, -game_area.limit_y - 50.0),
+            &game_area,
+        );
 
-        assert_eq!(vect_in_game_are.x, GAME_AREA_LIMIT_X);
-        assert_eq!(vect_in_game_are.y, GAME_AREA_LIMIT_Y);
+        assert_eq!(position, Vec2::new(game_area.limit_x, -game_area.limit_y));
     }
 }
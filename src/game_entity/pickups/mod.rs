@@ -0,0 +1,110 @@
+use crate::game_entity::MoveableSprite;
+use crate::game_entity::MoveableSpriteTrait;
+use crate::weapons::WeaponKind;
+
+use bevy::ecs::component::Component;
+use bevy::prelude::*;
+
+/// The bonus given by a pickup to the player
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PickupKind {
+    /// Replace the weapon of the player
+    Weapon(WeaponKind),
+    /// Refill the weapon of the player
+    AmmoRefill,
+    /// Give back some health to the player
+    Health,
+    /// The player runs faster for a while
+    SpeedBoost,
+}
+
+struct PickupInternalData {
+    kind: PickupKind,
+    remaining_lifetime: f32,
+}
+
+/// A bonus dropped by a killed ennemy, collected by the player on contact
+#[derive(Component)]
+pub struct Pickup {
+    sprite_data: MoveableSprite,
+    internal_data: PickupInternalData,
+}
+
+impl MoveableSpriteTrait for Pickup {
+    fn get_moveable_interface(&self) -> &MoveableSprite {
+        &self.sprite_data
+    }
+
+    fn get_moveable_interface_mut(&mut self) -> &mut MoveableSprite {
+        &mut self.sprite_data
+    }
+}
+
+impl Pickup {
+    /// Returns a new Pickup object, it does not move
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The bonus given to the player
+    /// * `lifetime` - Time before the pickup disappears, in seconds
+    /// * `hitbox_size` - The hitbox of the pickup
+    /// # Examples
+    ///
+    /// ```
+    ///     # use bevy::prelude::*;
+    ///     # use street_of_zombies::game_entity::pickups::{Pickup, PickupKind};
+    ///     let pickup = Pickup::new(PickupKind::Health, 10.0, Vec2::new(20., 20.));
+    /// ```
+    pub fn new(kind: PickupKind, lifetime: f32, hitbox_size: Vec2) -> Self {
+        Pickup {
+            sprite_data: MoveableSprite::new(0.0, Vec2::ZERO, hitbox_size),
+            internal_data: PickupInternalData {
+                kind,
+                remaining_lifetime: lifetime,
+            },
+        }
+    }
+
+    /// Get the bonus given to the player
+    pub fn get_kind(&self) -> PickupKind {
+        self.internal_data.kind
+    }
+
+    /// Reduce the lifetime of the pickup. Returns true when the pickup must disappear
+    ///
+    /// # Arguments
+    ///
+    /// * `time_elapsed_since_last_update` - The game time elapsed since the last update, in seconds
+    ///
+    pub fn update_lifetime(&mut self, time_elapsed_since_last_update: f32) -> bool {
+        self.internal_data.remaining_lifetime -= time_elapsed_since_last_update;
+
+        self.internal_data.remaining_lifetime <= 0.0
+    }
+
+    /// Get the time before the pickup disappears, in seconds
+    pub fn get_remaining_lifetime(&self) -> f32 {
+        self.internal_data.remaining_lifetime
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pickup_expires() {
+        let mut pickup = Pickup::new(PickupKind::Health, 1.0, Vec2::new(20., 20.));
+
+        assert!(!pickup.update_lifetime(0.6));
+        assert!(pickup.update_lifetime(0.6));
+    }
+
+    #[test]
+    fn pickup_does_not_move() {
+        let pickup = Pickup::new(PickupKind::SpeedBoost, 1.0, Vec2::new(20., 20.));
+
+        assert_eq!(pickup.get_moveable_interface().get_direction(), Vec2::ZERO);
+        assert_eq!(pickup.get_moveable_interface().get_speed(), 0.0);
+    }
+}
@@ -1,19 +1,13 @@
 use crate::game_entity::MoveableSprite;
 use crate::game_entity::MoveableSpriteTrait;
+use crate::game_system::game_config::{PlayerConfig, WeaponsConfig};
 
-use crate::weapons::Pistol;
-use crate::weapons::Weapon;
+use crate::weapons;
+use crate::weapons::{FireResult, Weapon, WeaponKind};
 
 use bevy::prelude::*;
 use bevy::ecs::component::Component;
 
-// Default pistol weapon data
-static PROJECTILE_SPEED: f32 = 700.0;
-static AMO_IN_WEAPON: u32 = 800;
-static LIMIT_OF_FIRE: u32 = 700;
-static FIRE_RATE: f32 = 0.18;
-static DEFAULT_PLAYER_HITBOX_SIZE: (f32, f32) = (35., 40.);
-
 /// The Main Character entity, Controllable by the player. - A Player object contains all the information dedicated to a the player.
 #[derive(Component)]
 pub struct Player {
@@ -21,8 +15,25 @@ pub struct Player {
     player_data: PlayerInternal,
 }
 
+/// A weapon carried by the player, with its own ammo
+struct InventorySlot {
+    kind: WeaponKind,
+    weapon: Box<dyn Weapon + Send + Sync>,
+}
+
 struct PlayerInternal {
-    current_weapon: Box<dyn Weapon + Send + Sync>,
+    inventory: Vec<InventorySlot>,
+    current_slot: usize,
+    inventory_size: usize,
+    weapon_switch_delay: f32,
+    weapon_switch_remaining_time: f32,
+    base_speed: f32,
+    speed_boost_remaining_time: f32,
+    invulnerability_duration: f32,
+    /// Time before the player can be hurt again
+    invulnerability_remaining_time: f32,
+    /// The direction of fire chosen by the player, None to fire in the direction of the movement
+    aim_direction: Option<Vec2>,
 }
 
 impl MoveableSpriteTrait for Player {
@@ -40,65 +51,245 @@ impl Player {
     ///
     /// # Arguments
     ///
-    /// * `speed_to_set` - The speed of the player
-    /// * `direction_to_set` - The direction of the player
-    /// * `initial_pos` - The initial position of the player
+    /// * `config` - The configuration of the player (speed, initial position and direction, weapon)
+    /// * `weapons_config` - The characteristics of the weapons
     /// # Examples
     ///
     /// ```
-    ///     let player = Player::new(500.0, (5., 10.), (15., 20.));
+    ///     # use street_of_zombies::game_entity::player::Player;
+    ///     # use street_of_zombies::game_system::game_config::{PlayerConfig, WeaponsConfig};
+    ///     let player = Player::new(&PlayerConfig::default(), &WeaponsConfig::default());
     /// ```
-    pub fn new(speed_to_set: f32, direction_to_set: (f32, f32), initial_pos: (f32, f32)) -> Self {
+    pub fn new(config: &PlayerConfig, weapons_config: &WeaponsConfig) -> Self {
         Player {
             player_data: PlayerInternal {
-                current_weapon: Box::new(Pistol::new(
-                    PROJECTILE_SPEED,
-                    FIRE_RATE,
-                    AMO_IN_WEAPON,
-                    LIMIT_OF_FIRE,
-                )),
+                inventory: vec![InventorySlot {
+                    kind: config.weapon,
+                    weapon: weapons::create_weapon(config.weapon, weapons_config.get(config.weapon)),
+                }],
+                current_slot: 0,
+                inventory_size: config.inventory_size,
+                weapon_switch_delay: config.weapon_switch_delay,
+                weapon_switch_remaining_time: 0.0,
+                base_speed: config.speed,
+                speed_boost_remaining_time: 0.0,
+                invulnerability_duration: config.invulnerability_duration,
+                invulnerability_remaining_time: 0.0,
+                aim_direction: None,
             },
             sprite_data: MoveableSprite::new(
-                speed_to_set,
-                direction_to_set,
-                initial_pos,
-                DEFAULT_PLAYER_HITBOX_SIZE,
+                config.speed,
+                config.initial_direction,
+                config.hitbox_size,
             ),
         }
     }
 
-    /// Launch a Projectile with the player weapon
+    /// Launch a Projectile with the player weapon. The player cannot fire while switching weapon
     ///
     /// # Arguments
     ///
     /// * `commands` - The bevy command object.
     /// * `time` - The timer generated by the bevy system.
-    /// ```
+    /// * `position` - The current position of the player, where the projectiles are launched
+    ///
     pub fn fire(
         &mut self,
         commands: &mut Commands,
         time: &Res<Time>,
-    ) {
-        self.player_data.current_weapon.fire_global(
+        position: Vec2,
+    ) -> FireResult {
+        if self.player_data.weapon_switch_remaining_time > 0.0 {
+            return FireResult::NotReady;
+        }
+
+        let direction = self.get_fire_direction();
+        self.get_current_weapon_mut().fire_global(
             commands,
             time,
-            self.sprite_data.get_direction(),
-            self.sprite_data.get_position(),
+            direction,
+            position,
             false,
             // Add a dummy argument if required by the trait
-        );
+        )
     }
 
-    /// Reload the player weapon
+    /// Aim in a direction, independently of the movement
+    ///
+    /// # Arguments
+    ///
+    /// * `aim_direction` - The direction of fire, None to fire in the direction of the movement
+    ///
+    pub fn set_aim_direction(&mut self, aim_direction: Option<Vec2>) {
+        self.player_data.aim_direction = aim_direction;
+    }
+
+    /// Get the direction of fire: the aim direction, or the direction of the movement when the player does not aim.
+    /// The player faces this direction. Its length is 1, a slow analog movement does not slow the projectiles down
+    pub fn get_fire_direction(&self) -> Vec2 {
+        let direction = self
+            .player_data
+            .aim_direction
+            .unwrap_or_else(|| self.sprite_data.get_direction());
+
+        direction.try_normalize().unwrap_or(direction)
+    }
+
+    /// Start to reload the player weapon, the reload takes the reload time of the weapon
     ///
     /// # Examples
     ///
     /// ```
-    ///     let player = Player::new(500.0, (5., 10.), (15., 20.));
-    ///     player.reload_weapon()
+    ///     # use street_of_zombies::game_entity::player::Player;
+    ///     # use street_of_zombies::game_system::game_config::{PlayerConfig, WeaponsConfig};
+    ///     let mut player = Player::new(&PlayerConfig::default(), &WeaponsConfig::default());
+    ///     player.reload_weapon();
     /// ```
     pub fn reload_weapon(&mut self) {
-        self.player_data.current_weapon.reload();
+        self.get_current_weapon_mut().start_reload();
+    }
+
+    /// Fill the reserve amo of the player weapon
+    pub fn refill_weapon_amo(&mut self) {
+        self.get_current_weapon_mut().refill_amo();
+    }
+
+    /// Add a weapon to the inventory and take it, the new weapon is loaded.
+    /// The reserve amo of a weapon already in the inventory is refilled, the current weapon is replaced when the inventory is full.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The type of the new weapon
+    /// * `weapon` - The new weapon
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///     # use street_of_zombies::game_entity::player::Player;
+    ///     # use street_of_zombies::game_system::game_config::{PlayerConfig, WeaponsConfig};
+    ///     # use street_of_zombies::weapons::{create_weapon, WeaponKind};
+    ///     let weapons_config = WeaponsConfig::default();
+    ///     let mut player = Player::new(&PlayerConfig::default(), &weapons_config);
+    ///     player.add_weapon(WeaponKind::Shotgun, create_weapon(WeaponKind::Shotgun, &weapons_config.shotgun));
+    /// ```
+    pub fn add_weapon(&mut self, kind: WeaponKind, weapon: Box<dyn Weapon + Send + Sync>) {
+        let inventory = &mut self.player_data.inventory;
+
+        let slot = match inventory.iter().position(|slot| slot.kind == kind) {
+            Some(slot) => slot,
+            None if inventory.len() < self.player_data.inventory_size => {
+                inventory.push(InventorySlot { kind, weapon });
+                inventory.len() - 1
+            }
+            None => {
+                inventory[self.player_data.current_slot] = InventorySlot { kind, weapon };
+                self.player_data.current_slot
+            }
+        };
+
+        self.select_weapon(slot);
+        self.refill_weapon_amo();
+    }
+
+    /// Take the weapon of an inventory slot. Nothing happens if the slot is empty or already selected
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - The inventory slot, from 0
+    ///
+    pub fn select_weapon(&mut self, slot: usize) {
+        if slot >= self.player_data.inventory.len() || slot == self.player_data.current_slot {
+            return;
+        }
+
+        self.player_data.current_slot = slot;
+        self.player_data.weapon_switch_remaining_time = self.player_data.weapon_switch_delay;
+    }
+
+    /// Take the weapon of the next inventory slot (the first one after the last one)
+    pub fn select_next_weapon(&mut self) {
+        let number_of_weapons = self.player_data.inventory.len();
+        self.select_weapon((self.player_data.current_slot + 1) % number_of_weapons);
+    }
+
+    /// Take the weapon of the previous inventory slot (the last one before the first one)
+    pub fn select_previous_weapon(&mut self) {
+        let number_of_weapons = self.player_data.inventory.len();
+        self.select_weapon((self.player_data.current_slot + number_of_weapons - 1) % number_of_weapons);
+    }
+
+    /// Get the inventory slot of the current weapon, from 0
+    pub fn get_current_slot(&self) -> usize {
+        self.player_data.current_slot
+    }
+
+    /// Get the number of weapons in the inventory
+    pub fn get_number_of_weapons(&self) -> usize {
+        self.player_data.inventory.len()
+    }
+
+    /// Get the type of the current weapon
+    pub fn get_current_weapon_kind(&self) -> WeaponKind {
+        self.player_data.inventory[self.player_data.current_slot].kind
+    }
+
+    /// Get the current weapon
+    pub fn get_current_weapon(&self) -> &(dyn Weapon + Send + Sync) {
+        self.player_data.inventory[self.player_data.current_slot].weapon.as_ref()
+    }
+
+    fn get_current_weapon_mut(&mut self) -> &mut Box<dyn Weapon + Send + Sync> {
+        &mut self.player_data.inventory[self.player_data.current_slot].weapon
+    }
+
+    /// The player runs faster for a while. A new boost restarts the duration
+    ///
+    /// # Arguments
+    ///
+    /// * `speed_factor` - The speed of the player is multiplied by this factor
+    /// * `duration` - The duration of the boost, in seconds
+    ///
+    pub fn apply_speed_boost(&mut self, speed_factor: f32, duration: f32) {
+        self.sprite_data.internal_data.speed = self.player_data.base_speed * speed_factor;
+        self.player_data.speed_boost_remaining_time = duration;
+    }
+
+    /// The player cannot be hurt for a while, after a hit
+    pub fn start_invulnerability(&mut self) {
+        self.player_data.invulnerability_remaining_time = self.player_data.invulnerability_duration;
+    }
+
+    /// Return true if the player cannot be hurt
+    pub fn is_invulnerable(&self) -> bool {
+        self.player_data.invulnerability_remaining_time > 0.0
+    }
+
+    /// Get the time before the player can be hurt again, in seconds
+    pub fn get_invulnerability_remaining_time(&self) -> f32 {
+        self.player_data.invulnerability_remaining_time
+    }
+
+    /// Update the timed effects (speed boost, invulnerability, weapon switch, reload) of the player
+    ///
+    /// # Arguments
+    ///
+    /// * `time_elapsed_since_last_update` - The game time elapsed since the last update, in seconds
+    ///
+    pub fn update_timed_effects(&mut self, time_elapsed_since_last_update: f32) {
+        self.player_data.weapon_switch_remaining_time =
+            (self.player_data.weapon_switch_remaining_time - time_elapsed_since_last_update).max(0.0);
+        self.get_current_weapon_mut().update_reload(time_elapsed_since_last_update);
+        self.player_data.invulnerability_remaining_time =
+            (self.player_data.invulnerability_remaining_time - time_elapsed_since_last_update).max(0.0);
+
+        if self.player_data.speed_boost_remaining_time <= 0.0 {
+            return;
+        }
+
+        self.player_data.speed_boost_remaining_time -= time_elapsed_since_last_update;
+        if self.player_data.speed_boost_remaining_time <= 0.0 {
+            self.sprite_data.internal_data.speed = self.player_data.base_speed;
+        }
     }
 }
 
@@ -108,12 +299,105 @@ mod tests {
 
     #[test]
     fn player_weapon_reload_test() {
-        let mut player = Player::new(500.0, (5., 10.), (15., 20.));
-        // Reload in case of "uncharged initial weapon"
-        player.reload_weapon();
-        let initial_amo = player.player_data.current_weapon.get_amo();
-        player.player_data.current_weapon.reduce_amo();
+        let weapons_config = WeaponsConfig::default();
+        let mut player = Player::new(&PlayerConfig::default(), &weapons_config);
+        // The weapon is loaded at the beginning
+        assert_eq!(player.get_current_weapon().get_amo(), weapons_config.pistol.magazine_size);
+
+        player.get_current_weapon_mut().reduce_amo();
         player.reload_weapon();
-        assert_eq!(player.player_data.current_weapon.get_amo(), initial_amo);
+        assert!(player.get_current_weapon().is_reloading());
+
+        player.update_timed_effects(weapons_config.pistol.reload_time);
+        assert_eq!(player.get_current_weapon().get_amo(), weapons_config.pistol.magazine_size);
+        assert_eq!(
+            player.get_current_weapon().get_reserve_amo(),
+            weapons_config.pistol.reserve_ammo.map(|reserve_ammo| reserve_ammo - 1)
+        );
+    }
+
+    #[test]
+    fn each_weapon_of_the_inventory_keeps_its_amo() {
+        let weapons_config = WeaponsConfig::default();
+        let mut player = Player::new(&PlayerConfig::default(), &weapons_config);
+        player.get_current_weapon_mut().reduce_amo();
+
+        player.add_weapon(
+            WeaponKind::Shotgun,
+            weapons::create_weapon(WeaponKind::Shotgun, &weapons_config.shotgun),
+        );
+        assert_eq!(player.get_current_weapon_kind(), WeaponKind::Shotgun);
+        assert_eq!(player.get_current_weapon().get_amo(), weapons_config.shotgun.magazine_size);
+
+        player.select_next_weapon();
+        assert_eq!(player.get_current_weapon_kind(), WeaponKind::Pistol);
+        assert_eq!(player.get_current_weapon().get_amo(), weapons_config.pistol.magazine_size - 1);
+    }
+
+    #[test]
+    fn full_inventory_replaces_the_current_weapon() {
+        let weapons_config = WeaponsConfig::default();
+        let config = PlayerConfig {
+            inventory_size: 2,
+            ..Default::default()
+        };
+        let mut player = Player::new(&config, &weapons_config);
+
+        for kind in [WeaponKind::Shotgun, WeaponKind::Shotgun, WeaponKind::SniperRifle] {
+            player.add_weapon(kind, weapons::create_weapon(kind, weapons_config.get(kind)));
+        }
+
+        assert_eq!(player.get_number_of_weapons(), 2);
+        assert_eq!(player.get_current_slot(), 1);
+        assert_eq!(player.get_current_weapon_kind(), WeaponKind::SniperRifle);
+        player.select_previous_weapon();
+        assert_eq!(player.get_current_weapon_kind(), WeaponKind::Pistol);
+    }
+
+    #[test]
+    fn aim_is_independent_of_the_movement() {
+        let config = PlayerConfig::default();
+        let mut player = Player::new(&config, &WeaponsConfig::default());
+        assert_eq!(player.get_fire_direction(), config.initial_direction);
+
+        player.set_aim_direction(Some(Vec2::new(1., 0.)));
+        player.get_moveable_interface_mut().set_new_direction(Vec2::new(0., -1.));
+        assert_eq!(player.get_fire_direction(), Vec2::new(1., 0.));
+
+        player.set_aim_direction(None);
+        assert_eq!(player.get_fire_direction(), Vec2::new(0., -1.));
+
+        // Half deflected stick
+        player.get_moveable_interface_mut().set_new_direction(Vec2::new(0., 0.5));
+        assert_eq!(player.get_fire_direction(), Vec2::new(0., 1.));
+    }
+
+    #[test]
+    fn speed_boost_is_timed() {
+        let config = PlayerConfig::default();
+        let mut player = Player::new(&config, &WeaponsConfig::default());
+
+        player.apply_speed_boost(2.0, 1.0);
+        assert_eq!(player.get_moveable_interface().get_speed(), config.speed * 2.0);
+
+        player.update_timed_effects(0.5);
+        assert_eq!(player.get_moveable_interface().get_speed(), config.speed * 2.0);
+
+        player.update_timed_effects(0.6);
+        assert_eq!(player.get_moveable_interface().get_speed(), config.speed);
+    }
+
+    #[test]
+    fn invulnerability_is_timed() {
+        let config = PlayerConfig::default();
+        let mut player = Player::new(&config, &WeaponsConfig::default());
+        assert!(!player.is_invulnerable());
+
+        player.start_invulnerability();
+        player.update_timed_effects(config.invulnerability_duration / 2.);
+        assert!(player.is_invulnerable());
+
+        player.update_timed_effects(config.invulnerability_duration);
+        assert!(!player.is_invulnerable());
     }
 }
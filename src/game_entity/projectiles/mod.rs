@@ -1,15 +1,29 @@
 use crate::game_entity::MoveableSprite;
 use crate::game_entity::MoveableSpriteTrait;
 
-use crate::game_system::math_and_generator;
+use crate::weapons;
 
 use bevy::ecs::component::Component;
+use bevy::prelude::*;
 
-static DEFAULT_PROJECTILE_HITBOX: (f32, f32) = (10., 10.);
+static DEFAULT_PROJECTILE_HITBOX: Vec2 = Vec2::new(10., 10.);
+static DEFAULT_PROJECTILE_SIZE: Vec2 = Vec2::new(5., 5.);
+static DEFAULT_PROJECTILE_COLOR: Color = Color::rgb(1.0, 1.0, 1.0);
 
 struct ProjectileInternalData {
     projectile_limit_distance: u32,
     is_from_ennemy: bool,
+    /// The position where the projectile was fired
+    initial_position: Vec2,
+    color: Color,
+    size: Vec2,
+    /// Health removed by the projectile
+    damage: u32,
+    /// Number of different entities hit before the projectile disappears
+    pierce_count: u32,
+    explosion_radius: Option<f32>,
+    /// The entities already hit by the projectile
+    entities_hit: Vec<Entity>,
 }
 
 #[derive(Component)]
@@ -35,52 +49,62 @@ impl Projectile {
     ///
     /// * `speed_to_set` - The speed of the projectile
     /// * `direction_to_set` - The direction of the projectile
-    /// * `current_position_to_set` - The initial position of the projectile
+    /// * `initial_position` - The initial position of the projectile, where it is spawned
     /// * `limit_of_fire` - The distance limit of the projectile. Will diseapear when this distance is reached.
     /// * `is_from_ennemy` - True if the projectile comes from an ennemy. Else, it comes from the player
     /// # Examples
     ///
     /// ```
-    ///     let projectile = Projectile::new(500.0, (5., 10.), (15., 20.), 500, false);
+    ///     # use bevy::prelude::*;
+    ///     # use street_of_zombies::game_entity::projectiles::Projectile;
+    ///     let projectile = Projectile::new(500.0, Vec2::new(5., 10.), Vec2::new(15., 20.), 500, false);
     /// ```
     pub fn new(
         speed_to_set: f32,
-        direction_to_set: (f32, f32),
-        current_position_to_set: (f32, f32),
+        direction_to_set: Vec2,
+        initial_position: Vec2,
         limit_of_fire: u32,
         is_from_ennemy: bool,
     ) -> Self {
         Projectile {
             internal_data: ProjectileInternalData {
                 projectile_limit_distance: limit_of_fire,
-                is_from_ennemy: is_from_ennemy,
+                is_from_ennemy,
+                initial_position,
+                color: weapons::get_projectile_color(DEFAULT_PROJECTILE_COLOR, is_from_ennemy),
+                size: DEFAULT_PROJECTILE_SIZE,
+                damage: 1,
+                pierce_count: 1,
+                explosion_radius: None,
+                entities_hit: Vec::new(),
             },
-            sprite_data: MoveableSprite::new(
-                speed_to_set,
-                direction_to_set,
-                current_position_to_set,
-                DEFAULT_PROJECTILE_HITBOX,
-            ),
+            sprite_data: MoveableSprite::new(speed_to_set, direction_to_set, DEFAULT_PROJECTILE_HITBOX),
         }
     }
 
+    /// Get the position where the projectile was fired
+    pub fn get_initial_position(&self) -> Vec2 {
+        self.internal_data.initial_position
+    }
+
     /// Check if the projectile reached its "limit_of_fire"
     ///
+    /// # Arguments
+    ///
+    /// * `position` - The current position of the projectile
+    ///
     /// # Examples
     ///
     /// ```
-    ///    let mut projectile = Projectile::new(500.0, (5., 10.), (15., 20.), 500, false);
-    ///    projectile.set_new_position((600., 600.));
-    ///    assert_eq!(projectile.is_out_of_distance(), true);
+    ///    # use bevy::prelude::*;
+    ///    # use street_of_zombies::game_entity::projectiles::Projectile;
+    ///    let projectile = Projectile::new(500.0, Vec2::new(5., 10.), Vec2::new(15., 20.), 500, false);
+    ///    assert!(projectile.is_out_of_distance(Vec2::new(600., 600.)));
     /// ```
-    pub fn is_out_of_distance(&self) -> bool {
-        let distance_walked = math_and_generator::calculate_cartesian_distance(
-            self.sprite_data.internal_data.initial_position,
-            self.sprite_data.internal_data.current_position,
-        );
+    pub fn is_out_of_distance(&self, position: Vec2) -> bool {
+        let distance_walked = self.internal_data.initial_position.distance(position);
 
-        let result = distance_walked > (self.internal_data.projectile_limit_distance as f32);
-        result
+        distance_walked > (self.internal_data.projectile_limit_distance as f32)
     }
 
     /// True if the projectile comes from an ennemy. Else, it comes from the player
@@ -88,40 +112,188 @@ impl Projectile {
     /// # Examples
     ///
     /// ```
-    ///    let mut projectile = Projectile::new(500.0, (5., 10.), (15., 20.), 500, false);
-    ///    assert_eq!(projectile.is_coming_from_ennemy(), false);
+    ///    # use bevy::prelude::*;
+    ///    # use street_of_zombies::game_entity::projectiles::Projectile;
+    ///    let projectile = Projectile::new(500.0, Vec2::new(5., 10.), Vec2::new(15., 20.), 500, false);
+    ///    assert!(!projectile.is_coming_from_ennemy());
     /// ```
     pub fn is_coming_from_ennemy(&self) -> bool {
         self.internal_data.is_from_ennemy
     }
+
+    /// Set the color and the size of the projectile. The hitbox grows with the big projectiles
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color of the projectile
+    /// * `size` - The size of the projectile
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///    # use bevy::prelude::*;
+    ///    # use street_of_zombies::game_entity::projectiles::Projectile;
+    ///    let projectile = Projectile::new(500.0, Vec2::new(5., 10.), Vec2::new(15., 20.), 500, false)
+    ///        .with_appearance(Color::rgb(1.0, 1.0, 0.0), Vec2::new(3., 3.));
+    /// ```
+    pub fn with_appearance(mut self, color: Color, size: Vec2) -> Self {
+        self.internal_data.color = color;
+        self.internal_data.size = size;
+        self.sprite_data.internal_data.hitbox_size = size.max(DEFAULT_PROJECTILE_HITBOX);
+        self
+    }
+
+    /// Set the health removed by the projectile on each hit
+    ///
+    /// # Arguments
+    ///
+    /// * `damage` - The health removed
+    ///
+    pub fn with_damage(mut self, damage: u32) -> Self {
+        self.internal_data.damage = damage;
+        self
+    }
+
+    /// Get the health removed by the projectile on each hit
+    pub fn get_damage(&self) -> u32 {
+        self.internal_data.damage
+    }
+
+    /// Set the number of different entities hit by the projectile before it disappears.
+    /// With more than 1, the projectile goes through the ennemies. Each entity is hit only once
+    ///
+    /// # Arguments
+    ///
+    /// * `pierce_count` - The number of entities, at least 1
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///    # use bevy::prelude::*;
+    ///    # use street_of_zombies::game_entity::projectiles::Projectile;
+    ///    let projectile = Projectile::new(1400.0, Vec2::new(0., 1.), Vec2::new(15., 20.), 1200, false)
+    ///        .with_pierce_count(3);
+    /// ```
+    pub fn with_pierce_count(mut self, pierce_count: u32) -> Self {
+        self.internal_data.pierce_count = pierce_count.max(1);
+        self
+    }
+
+    /// The projectile explodes on impact, hitting every ennemy in the radius of the explosion
+    ///
+    /// # Arguments
+    ///
+    /// * `radius` - The radius of the explosion
+    ///
+    pub fn with_explosion(mut self, radius: f32) -> Self {
+        self.internal_data.explosion_radius = Some(radius);
+        self
+    }
+
+    /// Get the color of the projectile
+    pub fn get_color(&self) -> Color {
+        self.internal_data.color
+    }
+
+    /// Get the size of the projectile
+    pub fn get_size(&self) -> Vec2 {
+        self.internal_data.size
+    }
+
+    /// True if the projectile hit as many entities as its pierce count: it cannot hit anything else
+    pub fn is_spent(&self) -> bool {
+        self.internal_data.entities_hit.len() >= self.internal_data.pierce_count as usize
+    }
+
+    /// Get the radius of the explosion on impact. None if the projectile does not explode
+    pub fn get_explosion_radius(&self) -> Option<f32> {
+        self.internal_data.explosion_radius
+    }
+
+    /// Register a hit on an entity. Returns false, without hit, if this entity was already hit by the projectile
+    /// or if the projectile is spent
+    ///
+    /// # Arguments
+    ///
+    /// * `entity` - The entity (ennemy or player) touched by the projectile
+    ///
+    pub fn register_hit(&mut self, entity: Entity) -> bool {
+        if self.is_spent() || self.internal_data.entities_hit.contains(&entity) {
+            return false;
+        }
+
+        self.internal_data.entities_hit.push(entity);
+        true
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    fn new_projectile(is_from_ennemy: bool) -> Projectile {
+        Projectile::new(500.0, Vec2::new(5., 10.), Vec2::new(15., 20.), 500, is_from_ennemy)
+    }
+
     #[test]
     fn projectile_out_of_fire_position() {
-        let mut projectile = Projectile::new(500.0, (5., 10.), (15., 20.), 500, false);
-        projectile.sprite_data.set_new_position((600., 600.));
-        assert_eq!(projectile.is_out_of_distance(), true);
+        let projectile = new_projectile(false);
+        assert!(projectile.is_out_of_distance(Vec2::new(600., 600.)));
     }
 
     #[test]
     fn projectile_still_in_fire() {
-        let mut projectile = Projectile::new(500.0, (5., 10.), (15., 20.), 500, false);
-        projectile.sprite_data.set_new_position((50., 20.));
-        assert_eq!(projectile.is_out_of_distance(), false);
+        let projectile = new_projectile(false);
+        assert!(!projectile.is_out_of_distance(Vec2::new(50., 20.)));
+    }
+
+    #[test]
+    fn fire_distance_is_not_rounded() {
+        let projectile = new_projectile(false);
+        // 500.4 from the initial position, 500 once rounded to integers
+        assert!(projectile.is_out_of_distance(Vec2::new(15. + 500.4, 20.)));
     }
 
     #[test]
     fn projectile_is_launched_by_ennemy() {
-        let projectile = Projectile::new(500.0, (5., 10.), (15., 20.), 500, true);
-        assert_eq!(projectile.is_coming_from_ennemy(), true);
+        let projectile = new_projectile(true);
+        assert!(projectile.is_coming_from_ennemy());
     }
 
     #[test]
     fn projectile_is_launched_by_player() {
-        let projectile = Projectile::new(500.0, (5., 10.), (15., 20.), 500, false);
-        assert_eq!(projectile.is_coming_from_ennemy(), false);
+        let projectile = new_projectile(false);
+        assert!(!projectile.is_coming_from_ennemy());
+    }
+
+    #[test]
+    fn big_projectile_has_a_big_hitbox() {
+        let projectile = new_projectile(false).with_appearance(Color::rgb(1.0, 0.5, 0.0), Vec2::new(20., 4.));
+
+        assert_eq!(projectile.get_size(), Vec2::new(20., 4.));
+        assert_eq!(
+            projectile.get_moveable_interface().get_hitbox_size(),
+            Vec2::new(20., DEFAULT_PROJECTILE_HITBOX.y)
+        );
+    }
+
+    #[test]
+    fn ennemy_hit_only_once() {
+        let mut projectile = new_projectile(false).with_pierce_count(3);
+        let ennemy = Entity::from_raw(7);
+
+        assert!(projectile.register_hit(ennemy));
+        assert!(!projectile.register_hit(ennemy));
+        assert!(projectile.register_hit(Entity::from_raw(8)));
+        assert!(!projectile.is_spent());
+    }
+
+    #[test]
+    fn projectile_spent_after_its_pierce_count() {
+        let mut projectile = new_projectile(false);
+
+        assert!(projectile.register_hit(Entity::from_raw(7)));
+        assert!(projectile.is_spent());
+        assert!(!projectile.register_hit(Entity::from_raw(8)));
     }
 }
@@ -0,0 +1,197 @@
+use crate::game_entity::health::Health;
+use crate::game_entity::pickups::{Pickup, PickupKind};
+use crate::game_entity::*;
+use crate::game_system::game_config::{GameConfig, PickupsConfig};
+use crate::game_system::gameplay_events::EnnemyKilled;
+use crate::game_system::projectile_and_kill_gameplay::is_entities_collides;
+use crate::game_system::*;
+use crate::weapons::{self, WeaponKind};
+
+use bevy::prelude::*;
+use rand::Rng;
+
+/// The weapons which can be dropped by a killed ennemy
+static DROPPABLE_WEAPONS: [WeaponKind; 4] = [
+    WeaponKind::Shotgun,
+    WeaponKind::SubmachineGun,
+    WeaponKind::SniperRifle,
+    WeaponKind::RocketLauncher,
+];
+
+/// Game System: Roll the drop table of each killed ennemy and spawn the pickups. Managed by as a "Bevy System"
+pub fn drop_pickup_system(
+    mut commands: Commands,
+    mut killed_events: EventReader<EnnemyKilled>,
+    mut game_rng: ResMut<math_and_generator::GameRng>,
+    game_config: Res<GameConfig>,
+) {
+    for killed_ennemy in killed_events.read() {
+        if let Some(kind) = roll_drop_table(game_rng.generator(), &game_config.pickups) {
+            commands
+                .spawn(TransformBundle::from_transform(Transform::from_translation(
+                    killed_ennemy.position.extend(0.0),
+                )))
+                .insert(Pickup::new(kind, game_config.pickups.lifetime, game_config.pickups.hitbox_size));
+        }
+    }
+}
+
+/// Game System: The player collects the pickups on contact. Managed by as a "Bevy System"
+pub fn pickup_collection_system(
+    mut commands: Commands,
+    mut player_query: Query<(&mut player::Player, &mut Health, &Transform)>,
+    pickup_query: Query<(Entity, &Pickup, &Transform)>,
+    game_config: Res<GameConfig>,
+) {
+    for (mut player, mut health, player_transform) in player_query.iter_mut() {
+        for (pickup_entity, pickup, pickup_transform) in pickup_query.iter() {
+            if is_entities_collides(
+                (player_transform, player.get_moveable_interface()),
+                (pickup_transform, pickup.get_moveable_interface()),
+            ) {
+                apply_pickup(&mut player, &mut health, pickup.get_kind(), &game_config);
+                commands.entity(pickup_entity).despawn();
+            }
+        }
+    }
+}
+
+/// Game System: The pickups disappear after a while. Managed by as a "Bevy System"
+pub fn pickup_timeout_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pickup_query: Query<(Entity, &mut Pickup)>,
+) {
+    for (pickup_entity, mut pickup) in pickup_query.iter_mut() {
+        if pickup.update_lifetime(time.delta_seconds()) {
+            commands.entity(pickup_entity).despawn();
+        }
+    }
+}
+
+/// Game System: The timed effects of the player (speed boost, weapon switch) expire. Managed by as a "Bevy System"
+pub fn player_timed_effects_system(time: Res<Time>, mut player_query: Query<&mut player::Player>) {
+    for mut player in player_query.iter_mut() {
+        player.update_timed_effects(time.delta_seconds());
+    }
+}
+
+/// Roll the drop table of a killed ennemy. Returns the kind of the dropped pickup, if any
+///
+/// # Arguments
+///
+/// * `rng` - The random number generator
+/// * `config` - The drop chance and the weight of each pickup
+///
+/// # Examples
+///
+/// ```ignore
+///     let dropped_pickup = roll_drop_table(game_rng.generator(), &GameConfig::default().pickups);
+/// ```
+fn roll_drop_table(rng: &mut impl Rng, config: &PickupsConfig) -> Option<PickupKind> {
+    let total_weight = config.weapon_weight
+        + config.ammo_refill_weight
+        + config.health_weight
+        + config.speed_boost_weight;
+
+    if total_weight == 0 || !rng.gen_bool(config.drop_chance.clamp(0.0, 1.0) as f64) {
+        return None;
+    }
+
+    let mut roll = rng.gen_range(0..total_weight);
+    if roll < config.weapon_weight {
+        let weapon = DROPPABLE_WEAPONS[rng.gen_range(0..DROPPABLE_WEAPONS.len())];
+        return Some(PickupKind::Weapon(weapon));
+    }
+    roll -= config.weapon_weight;
+    if roll < config.ammo_refill_weight {
+        return Some(PickupKind::AmmoRefill);
+    }
+    roll -= config.ammo_refill_weight;
+    if roll < config.health_weight {
+        return Some(PickupKind::Health);
+    }
+
+    Some(PickupKind::SpeedBoost)
+}
+
+fn apply_pickup(
+    player: &mut player::Player,
+    health: &mut Health,
+    kind: PickupKind,
+    game_config: &GameConfig,
+) {
+    match kind {
+        PickupKind::Weapon(weapon_kind) => player.add_weapon(
+            weapon_kind,
+            weapons::create_weapon(weapon_kind, game_config.weapons.get(weapon_kind)),
+        ),
+        PickupKind::AmmoRefill => player.refill_weapon_amo(),
+        PickupKind::Health => health.heal(game_config.pickups.health_restored),
+        PickupKind::SpeedBoost => player.apply_speed_boost(
+            game_config.pickups.speed_boost_factor,
+            game_config.pickups.speed_boost_duration,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_system::game_config::PlayerConfig;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn drop_table_follows_the_drop_chance() {
+        let mut game_rng = math_and_generator::GameRng::new(42);
+        let never_drop = PickupsConfig {
+            drop_chance: 0.0,
+            ..Default::default()
+        };
+        let only_health = PickupsConfig {
+            drop_chance: 1.0,
+            weapon_weight: 0,
+            ammo_refill_weight: 0,
+            speed_boost_weight: 0,
+            ..Default::default()
+        };
+
+        for _ in 0..100 {
+            assert_eq!(roll_drop_table(game_rng.generator(), &never_drop), None);
+            assert_eq!(roll_drop_table(game_rng.generator(), &only_health), Some(PickupKind::Health));
+        }
+    }
+
+    #[test]
+    fn player_collects_the_pickup_on_contact() {
+        let game_config = GameConfig::default();
+        let mut world = World::new();
+        world.insert_resource(game_config.clone());
+
+        let mut health = Health::new(game_config.player.health);
+        health.take_damage(2);
+        let player_transform = Transform::from_translation(game_config.player.initial_position.extend(0.));
+        world.spawn((
+            player::Player::new(&PlayerConfig::default(), &game_config.weapons),
+            health,
+            player_transform,
+        ));
+        world.spawn((
+            Pickup::new(PickupKind::Health, game_config.pickups.lifetime, game_config.pickups.hitbox_size),
+            player_transform,
+        ));
+        // Far from the player
+        world.spawn((
+            Pickup::new(PickupKind::Health, game_config.pickups.lifetime, game_config.pickups.hitbox_size),
+            Transform::from_xyz(300., 200., 0.),
+        ));
+
+        world.run_system_once(pickup_collection_system);
+
+        assert_eq!(
+            world.query::<&Health>().single(&world).get_current(),
+            game_config.player.health - 2 + game_config.pickups.health_restored
+        );
+        assert_eq!(world.query::<&Pickup>().iter(&world).count(), 1);
+    }
+}
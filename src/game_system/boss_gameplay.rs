@@ -0,0 +1,223 @@
+use crate::game_entity::ennemies::boss::{Boss, BossAttacks};
+use crate::game_entity::ennemies::{Ennemy, EnnemyKind};
+use crate::game_entity::health::Health;
+use crate::game_entity::projectiles::Projectile;
+use crate::game_entity::*;
+use crate::game_system::ennemy_spawn_ai_gameplay::generate_new_ennemy;
+use crate::game_system::game_config::GameConfig;
+use crate::game_system::gameplay_events::{DifficultyIncreased, ShotFired};
+use crate::game_system::math_and_generator;
+use crate::weapons;
+
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+/// Distance of the spawn of a boss from the center of the game area, relative to the height of the game area
+static BOSS_SPAWN_DISTANCE_FACTOR: f32 = 0.75;
+
+/// Game System: Start a boss fight when one of the difficulty levels of the `BossConfig` is reached.
+/// The boss comes from the other half of the game area than the player, a single boss at once. Managed by as a "Bevy System"
+pub fn boss_encounter_system(
+    mut commands: Commands,
+    mut difficulty_events: EventReader<DifficultyIncreased>,
+    boss_query: Query<(), With<Boss>>,
+    player_query: Query<&Transform, With<player::Player>>,
+    mut game_rng: ResMut<math_and_generator::GameRng>,
+    game_config: Res<GameConfig>,
+) {
+    let boss_levels_reached = difficulty_events
+        .read()
+        .filter(|difficulty_increased| {
+            game_config
+                .boss
+                .difficulty_levels
+                .contains(&difficulty_increased.difficulty_level)
+        })
+        .count();
+
+    if boss_levels_reached == 0 || !boss_query.is_empty() {
+        return;
+    }
+
+    let player_position = player_query.get_single().map_or(Vec2::ZERO, get_position);
+    let boss_side = if player_position.y > 0. { -1. } else { 1. };
+    let boss_position = Vec2::new(0., boss_side * game_config.game_area.limit_y * BOSS_SPAWN_DISTANCE_FACTOR);
+
+    let boss_entity = generate_new_ennemy(
+        &mut commands,
+        &mut game_rng,
+        EnnemyKind::Boss,
+        boss_position,
+        &game_config,
+    );
+    commands
+        .entity(boss_entity)
+        .insert(Boss::new(&game_config.boss.phases));
+}
+
+/// Game System: The bosses fire their radial bursts and summon their minions, following the phase of their fight.
+/// The minions are not summoned beyond the maximum number of ennemies. Managed by as a "Bevy System"
+pub fn boss_attack_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut boss_query: Query<(&mut Boss, &Health, &Transform)>,
+    ennemy_query: Query<(), With<Ennemy>>,
+    mut shot_fired_events: EventWriter<ShotFired>,
+    mut game_rng: ResMut<math_and_generator::GameRng>,
+    game_config: Res<GameConfig>,
+) {
+    let mut number_of_ennemies = ennemy_query.iter().count();
+
+    for (mut boss, health, transform) in boss_query.iter_mut() {
+        // A dead boss is despawned at the end of the tick
+        if health.is_dead() {
+            continue;
+        }
+
+        let attacks = boss.update(time.delta_seconds(), health, &game_config.boss.phases);
+        let boss_position = get_position(transform);
+
+        if attacks.burst_projectiles > 0 {
+            fire_radial_burst(&mut commands, boss_position, &attacks, &game_config);
+            shot_fired_events.send(ShotFired { is_from_ennemy: true });
+        }
+
+        let summoned_minions = (attacks.summoned_minions as usize)
+            .min(game_config.ennemy.maximum_number.saturating_sub(number_of_ennemies));
+        number_of_ennemies += summoned_minions;
+
+        for minion in 0..summoned_minions {
+            let angle = TAU * minion as f32 / summoned_minions as f32;
+            let minion_position = get_minion_position(boss_position, angle, &game_config);
+            generate_new_ennemy(
+                &mut commands,
+                &mut game_rng,
+                game_config.boss.minion_kind,
+                minion_position,
+                &game_config,
+            );
+        }
+    }
+}
+
+/// Fire the projectiles of a radial burst, evenly spread all around the boss
+fn fire_radial_burst(commands: &mut Commands, boss_position: Vec2, attacks: &BossAttacks, game_config: &GameConfig) {
+    let angle_between_projectiles = TAU / attacks.burst_projectiles as f32;
+
+    for projectile in 0..attacks.burst_projectiles {
+        let angle = attacks.burst_angle + projectile as f32 * angle_between_projectiles;
+        let projectile_direction = math_and_generator::rotate_direction(Vec2::X, angle);
+
+        weapons::spawn_projectile(
+            commands,
+            Projectile::new(
+                game_config.boss.burst_projectile_speed,
+                projectile_direction,
+                boss_position,
+                game_config.boss.burst_fire_distance,
+                true,
+            )
+            .with_damage(game_config.boss.burst_damage),
+        );
+    }
+}
+
+/// Get the position of a summoned minion around the boss, inside of the game area
+fn get_minion_position(boss_position: Vec2, angle: f32, game_config: &GameConfig) -> Vec2 {
+    let game_area_limits = Vec2::new(game_config.game_area.limit_x, game_config.game_area.limit_y);
+    let summon_offset = math_and_generator::rotate_direction(Vec2::X, angle) * game_config.boss.summon_distance;
+
+    (boss_position + summon_offset).clamp(-game_area_limits, game_area_limits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_system::game_config::EnnemyConfig;
+    use bevy::ecs::system::RunSystemOnce;
+
+    fn init_boss_world(world: &mut World) {
+        world.init_resource::<Events<DifficultyIncreased>>();
+        world.init_resource::<Events<ShotFired>>();
+        world.insert_resource(math_and_generator::GameRng::new(7));
+        world.insert_resource(GameConfig::default());
+    }
+
+    #[test]
+    fn boss_shows_up_at_its_difficulty_levels() {
+        let mut world = World::new();
+        init_boss_world(&mut world);
+        let boss_levels = GameConfig::default().boss.difficulty_levels;
+
+        world.send_event(DifficultyIncreased { difficulty_level: boss_levels[0] - 1 });
+        world.run_system_once(boss_encounter_system);
+        assert_eq!(world.query::<&Boss>().iter(&world).count(), 0);
+
+        world.send_event(DifficultyIncreased { difficulty_level: boss_levels[0] });
+        world.run_system_once(boss_encounter_system);
+        let mut boss_query = world.query::<(&Ennemy, &Boss)>();
+        let (boss_ennemy, _) = boss_query.single(&world);
+        assert_eq!(boss_ennemy.get_kind(), EnnemyKind::Boss);
+
+        // A single boss at once
+        world.send_event(DifficultyIncreased { difficulty_level: boss_levels[1] });
+        world.run_system_once(boss_encounter_system);
+        assert_eq!(world.query::<&Boss>().iter(&world).count(), 1);
+    }
+
+    #[test]
+    fn boss_fires_radial_bursts_and_summons_minions() {
+        let mut world = World::new();
+        init_boss_world(&mut world);
+        let mut time = Time::<()>::default();
+        time.advance_by(std::time::Duration::from_millis(100));
+        world.insert_resource(time);
+
+        let phases = GameConfig::default().boss.phases;
+        let mut health = Health::new(100);
+        // Last phase: bursts and minions at once
+        health.take_damage(100 - phases[2].health_percent);
+        world.spawn((Boss::new(&phases), health, Transform::from_xyz(0., 0., 0.)));
+
+        world.run_system_once(boss_attack_system);
+
+        let mut projectile_query = world.query::<&Projectile>();
+        assert_eq!(projectile_query.iter(&world).count(), phases[2].burst_projectiles as usize);
+        assert!(projectile_query.iter(&world).all(|projectile| projectile.is_coming_from_ennemy()));
+        let mut minion_query = world.query::<&Ennemy>();
+        assert_eq!(minion_query.iter(&world).count(), phases[2].summoned_minions as usize);
+        assert_eq!(world.resource::<Events<ShotFired>>().len(), 1);
+    }
+
+    #[test]
+    fn boss_does_not_summon_beyond_the_maximum_number_of_ennemies() {
+        let mut world = World::new();
+        init_boss_world(&mut world);
+        let mut time = Time::<()>::default();
+        time.advance_by(std::time::Duration::from_millis(100));
+        world.insert_resource(time);
+
+        let phases = GameConfig::default().boss.phases;
+        assert!(phases[2].summoned_minions > 1);
+        let mut health = Health::new(100);
+        health.take_damage(100 - phases[2].health_percent);
+        let mut game_config = GameConfig::default();
+        // The boss is an ennemy too, a single minion is summoned
+        game_config.ennemy.maximum_number = 2;
+        world.insert_resource(game_config);
+        world.spawn((
+            Ennemy::new(&EnnemyConfig::default(), EnnemyKind::Boss, Vec2::ZERO, Vec2::ZERO),
+            Boss::new(&phases),
+            health,
+            Transform::from_xyz(0., 0., 0.),
+        ));
+
+        world.run_system_once(boss_attack_system);
+        assert_eq!(world.query::<&Ennemy>().iter(&world).count(), 2);
+
+        // Nothing is summoned once the maximum is reached
+        world.resource_mut::<Time>().advance_by(std::time::Duration::from_secs(60));
+        world.run_system_once(boss_attack_system);
+        assert_eq!(world.query::<&Ennemy>().iter(&world).count(), 2);
+    }
+}
@@ -1,121 +1,399 @@
+use crate::game_entity::health::Health;
 use crate::game_entity::*;
+use crate::game_system::game_config::{EnnemyConfig, GameConfig};
+use crate::game_system::gameplay_events::ShotFired;
 use crate::game_system::*;
-use crate::sprite_manager_system::*;
+use crate::weapons::FireResult;
 use rand::Rng;
 
 use bevy::prelude::*;
 
-static MAXIMUM_NUMBER_OF_ENNEMIES: usize = 40;
+/// The position and the velocity of the player, aimed by the ennemies
+struct AiTarget {
+    position: Vec2,
+    velocity: Vec2,
+}
 
-/// Game System: AI management for ennemies  and manage the part "Difficulty" of the score system. Managed by as a "Bevy System"
+/// Game System: AI management for ennemies  and manage the part "Difficulty" of the score system.
+/// Each ennemy wanders until it sees the player, then chases it, fires at it or runs away from it.
+/// No new ennemy spawns during a boss fight. Managed by as a "Bevy System"
+#[allow(clippy::too_many_arguments)]
 pub fn ennemy_ai_system(
     mut commands: Commands,
-    mut materials: ResMut<Assets<ColorMaterial>>,
     time: Res<Time>,
     mut ennemy_query: Query<(&mut ennemies::Ennemy, &mut Transform)>,
-    scoreboard_query: Query<&scoreboard::ScoreAndInfo>,
-    asset_server: Res<AssetServer>,
-    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    player_query: Query<(&player::Player, &Transform), Without<ennemies::Ennemy>>,
+    current_scoreboard: Res<scoreboard::ScoreAndInfo>,
+    mut shot_fired_events: EventWriter<ShotFired>,
+    mut game_rng: ResMut<math_and_generator::GameRng>,
+    game_config: Res<GameConfig>,
 ) {
-    if let Ok(current_scoreboard) = scoreboard_query.get_single() {
-        movement_of_ennemies(&mut commands, &mut materials, &time, &mut ennemy_query);
-
-        let ennemies_spawned = ennemy_query.iter_mut().count();
-
-        if ennemies_spawned < MAXIMUM_NUMBER_OF_ENNEMIES {
-            ennemy_spawn_system(
-                &mut commands,
-                current_scoreboard.get_difficulty_level(),
-                &asset_server,
-                &mut texture_atlases,
-            );
+    let target = player_query.get_single().ok().map(|(player, player_transform)| AiTarget {
+        position: get_position(player_transform),
+        velocity: player
+            .get_moveable_interface()
+            .get_velocity(player_transform, time.delta_seconds()),
+    });
+    let aim_accuracy = game_config
+        .ennemy
+        .get_aim_accuracy(current_scoreboard.get_difficulty_level());
+
+    for (mut ennemy, mut ennemy_transform) in ennemy_query.iter_mut() {
+        ennemy.update_weapon(time.delta_seconds());
+        let ennemy_position = get_position(&ennemy_transform);
+        let distance_to_player = target.as_ref().map(|target| target.position.distance(ennemy_position));
+
+        match ennemy.get_ai_state(distance_to_player, game_config.ennemy.detection_radius) {
+            ennemies::EnnemyAiState::Wander => wander(&time, &mut ennemy, &mut ennemy_transform, &game_config),
+            ennemies::EnnemyAiState::Chase => {
+                let direction = get_direction_to(ennemy_position, target.as_ref());
+                move_ennemy(&time, &mut ennemy, &mut ennemy_transform, direction, &game_config);
+            }
+            ennemies::EnnemyAiState::Flee => {
+                let direction = -get_direction_to(ennemy_position, target.as_ref());
+                move_ennemy(&time, &mut ennemy, &mut ennemy_transform, direction, &game_config);
+            }
+            ennemies::EnnemyAiState::Attack => {
+                let Some(target) = target.as_ref() else {
+                    continue;
+                };
+                let aim_direction = get_aim_direction(
+                    game_rng.generator(),
+                    ennemy_position,
+                    target,
+                    ennemy.get_projectile_speed().unwrap_or(0.),
+                    aim_accuracy,
+                    game_config.ennemy.maximum_aim_error,
+                );
+
+                // The ennemy stands still and faces the player while firing
+                ennemy.get_moveable_interface_mut().stop(&ennemy_transform);
+                ennemy.get_moveable_interface_mut().set_new_direction(aim_direction);
+                if ennemy.launch_attack(&mut commands, &time, ennemy_position, aim_direction) == FireResult::Fired {
+                    shot_fired_events.send(ShotFired { is_from_ennemy: true });
+                }
+            }
         }
     }
+
+    let ennemies_spawned = ennemy_query.iter_mut().count();
+    // The regular spawns are paused during a boss fight
+    let is_boss_fight = ennemy_query
+        .iter()
+        .any(|(ennemy, _)| ennemy.get_kind() == ennemies::EnnemyKind::Boss);
+
+    if !is_boss_fight && ennemies_spawned < game_config.ennemy.maximum_number {
+        ennemy_spawn_system(
+            &mut commands,
+            &mut game_rng,
+            current_scoreboard.get_difficulty_level(),
+            &game_config,
+        );
+    }
 }
 
-fn movement_of_ennemies(
-    commands: &mut Commands,
-    _materials: &mut ResMut<Assets<ColorMaterial>>,
+/// The ennemy walks in its direction and turns back when it goes too far from its spawn position or reaches the game limit
+fn wander(
     time: &Res<Time>,
-    ennemy_query: &mut Query<(&mut ennemies::Ennemy, &mut Transform)>,
+    ennemy: &mut ennemies::Ennemy,
+    ennemy_transform: &mut Transform,
+    game_config: &GameConfig,
 ) {
-    for (mut ennemy, mut ennemy_transform) in ennemy_query.iter_mut() {
-        let ennemy_direction = ennemy.get_moveable_interface().get_direction();
-        ennemy.get_moveable_interface_mut().move_sprite(
-            time,
-            &ennemy_direction,
-            &mut ennemy_transform.translation,
-        );
+    let ennemy_direction = ennemy.get_moveable_interface().get_direction();
+    move_ennemy(time, ennemy, ennemy_transform, ennemy_direction, game_config);
+    let ennemy_position = get_position(ennemy_transform);
+    let offset_from_spawn = ennemy_position - ennemy.get_initial_position();
 
-        if (math_and_generator::calculate_cartesian_distance(
-            ennemy.get_initial_position(),
-            ennemy.get_moveable_interface().get_position(),
-        ) > MAXIMUM_ENNEMY_DISTANCE)
-            || (is_next_movement_out_of_game_area(
-                ennemy.get_moveable_interface().get_position(),
-                ennemy_direction,
-            ))
-        {
-            // Reverse direction
-            ennemy
-                .get_moveable_interface_mut()
-                .set_new_direction((-ennemy_direction.0, -ennemy_direction.1));
-        }
-
-        // Attack
-        ennemy.launch_attack(commands, time)
+    // After a chase, the ennemy can be far from its spawn position: it only turns back when it goes away from it
+    if (offset_from_spawn.length() > game_config.ennemy.maximum_distance && offset_from_spawn.dot(ennemy_direction) > 0.)
+        || (is_next_movement_out_of_game_area(ennemy_position, ennemy_direction, &game_config.game_area))
+    {
+        // Reverse direction
+        ennemy.get_moveable_interface_mut().set_new_direction(-ennemy_direction);
     }
 }
 
+fn move_ennemy(
+    time: &Res<Time>,
+    ennemy: &mut ennemies::Ennemy,
+    ennemy_transform: &mut Transform,
+    direction: Vec2,
+    game_config: &GameConfig,
+) {
+    ennemy
+        .get_moveable_interface_mut()
+        .move_sprite(time, direction, ennemy_transform, &game_config.game_area);
+}
+
+/// Get the direction from the ennemy to the player, the direction of fire of the ennemies by default
+fn get_direction_to(ennemy_position: Vec2, target: Option<&AiTarget>) -> Vec2 {
+    target
+        .and_then(|target| (target.position - ennemy_position).try_normalize())
+        .unwrap_or(Vec2::NEG_Y)
+}
+
+/// Get the direction of fire of an ennemy: a leading shot at the player, deviated following the accuracy
+///
+/// # Arguments
+///
+/// * `rng` - The random number generator
+/// * `ennemy_position` - The position of the ennemy, where the projectiles are launched
+/// * `target` - The position and the velocity of the player
+/// * `projectile_speed` - The speed of the projectiles of the ennemy
+/// * `aim_accuracy` - From 0 to 1 for a perfect leading shot
+/// * `maximum_aim_error` - The largest deviation of a shot with an accuracy of 0, in degrees
+///
+fn get_aim_direction(
+    rng: &mut impl Rng,
+    ennemy_position: Vec2,
+    target: &AiTarget,
+    projectile_speed: f32,
+    aim_accuracy: f32,
+    maximum_aim_error: f32,
+) -> Vec2 {
+    let leading_direction = math_and_generator::get_intercept_direction(
+        ennemy_position,
+        target.position,
+        target.velocity,
+        projectile_speed,
+    );
+    let aim_error = rng.gen_range(-1.0..=1.0) * (1.0 - aim_accuracy) * maximum_aim_error.to_radians();
+
+    math_and_generator::rotate_direction(leading_direction, aim_error)
+}
+
 fn ennemy_spawn_system(
     commands: &mut Commands,
+    game_rng: &mut math_and_generator::GameRng,
     difficulty_level: u32,
-    asset_server: &Res<AssetServer>,
-    texture_atlases: &mut ResMut<Assets<TextureAtlas>>,
+    game_config: &GameConfig,
 ) {
-    static SPAWN_FACTOR_CLASSIC_ENNEMY: u32 = 1100;
-    let generated_spawn_factor = SPAWN_FACTOR_CLASSIC_ENNEMY - (200 * difficulty_level);
+    let generated_spawn_factor = game_config.ennemy.spawn_factor.saturating_sub(
+        game_config.ennemy.spawn_factor_decrease_per_level.saturating_mul(difficulty_level),
+    );
 
-    let mut rng = rand::thread_rng();
-    let rand_system = rng.gen_range(0..generated_spawn_factor);
+    let rand_system = game_rng.generator().gen_range(0..generated_spawn_factor.max(1));
 
     if rand_system <= 2 {
-        generate_new_ennemy(commands, asset_server, texture_atlases);
+        let kind = roll_ennemy_kind(game_rng.generator(), &game_config.ennemy, difficulty_level);
+        let ennemy_initial_position = math_and_generator::generate_random_position(
+            game_rng.generator(),
+            game_config.game_area.limit_x,
+            game_config.game_area.limit_y,
+        );
+        generate_new_ennemy(commands, game_rng, kind, ennemy_initial_position, game_config);
     }
 }
 
-fn generate_new_ennemy(
+/// Choose the archetype of a new ennemy, following the spawn weights of the difficulty level.
+/// A walker spawns when every weight is 0
+///
+/// # Arguments
+///
+/// * `rng` - The random number generator
+/// * `config` - The spawn weights of each archetype
+/// * `difficulty_level` - The current difficulty level
+///
+fn roll_ennemy_kind(rng: &mut impl Rng, config: &EnnemyConfig, difficulty_level: u32) -> ennemies::EnnemyKind {
+    let total_weight: u32 = ennemies::ENNEMY_KINDS
+        .iter()
+        .map(|kind| config.get(*kind).get_spawn_weight(difficulty_level))
+        .sum();
+
+    if total_weight == 0 {
+        return ennemies::EnnemyKind::Walker;
+    }
+
+    let mut roll = rng.gen_range(0..total_weight);
+    for kind in ennemies::ENNEMY_KINDS {
+        let spawn_weight = config.get(kind).get_spawn_weight(difficulty_level);
+        if roll < spawn_weight {
+            return kind;
+        }
+        roll -= spawn_weight;
+    }
+
+    ennemies::EnnemyKind::Walker
+}
+
+/// Spawn a new ennemy, with the health of its archetype and a random direction. Returns the entity of the ennemy
+///
+/// # Arguments
+///
+/// * `commands` - The bevy command
+/// * `game_rng` - The random number generator of the game
+/// * `kind` - The archetype of the ennemy
+/// * `ennemy_initial_position` - The position where the ennemy spawns
+/// * `game_config` - The configuration of the game
+///
+pub fn generate_new_ennemy(
     commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
-    texture_atlases: &mut ResMut<Assets<TextureAtlas>>,
-) {
+    game_rng: &mut math_and_generator::GameRng,
+    kind: ennemies::EnnemyKind,
+    ennemy_initial_position: Vec2,
+    game_config: &GameConfig,
+) -> Entity {
     // Random generation
-    let ennemy_initial_position: (f32, f32) =
-        math_and_generator::generate_random_position(GAME_AREA_LIMIT_X, GAME_AREA_LIMIT_Y);
-    let ennemy_initial_direction: (f32, f32) =
-        math_and_generator::generate_random_direction_factor();
+    let ennemy_initial_direction = math_and_generator::generate_random_direction_factor(game_rng.generator());
 
     // Ennemy
+    let archetype = game_config.ennemy.get(kind);
     commands
-        .spawn(SpriteSheetBundle {
-            texture_atlas: generate_texture(
-                asset_server,
-                texture_atlases,
-                TextureToGenerate::ZOMBIE,
-            ),
-            transform: Transform::from_xyz(
-                ennemy_initial_position.0,
-                ennemy_initial_position.1,
-                0.0,
-            ),
-            sprite: TextureAtlasSprite::new(1),
-            ..Default::default()
-        })
+        .spawn(TransformBundle::from_transform(Transform::from_translation(
+            ennemy_initial_position.extend(0.0),
+        )))
         .insert(ennemies::Ennemy::new(
-            INITIAL_ENNEMY_SPEED,
+            &game_config.ennemy,
+            kind,
             ennemy_initial_direction,
             ennemy_initial_position,
-            50,
         ))
-        .insert(AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)));
+        .insert(
+            Health::new(archetype.health)
+                .with_regen(archetype.health_regen)
+                .with_armor(archetype.armor),
+        )
+        .id()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spawned_archetypes_follow_the_difficulty_level() {
+        let config = EnnemyConfig::default();
+        let mut game_rng = math_and_generator::GameRng::new(7);
+
+        for _ in 0..200 {
+            let kind = roll_ennemy_kind(game_rng.generator(), &config, 0);
+            assert!(config.get(kind).get_spawn_weight(0) > 0);
+        }
+
+        let hardest_kinds: Vec<ennemies::EnnemyKind> = (0..500)
+            .map(|_| roll_ennemy_kind(game_rng.generator(), &config, 5))
+            .collect();
+        for kind in ennemies::ENNEMY_KINDS {
+            assert!(hardest_kinds.contains(&kind));
+        }
+    }
+
+    #[test]
+    fn accurate_ennemy_leads_its_shots() {
+        let mut game_rng = math_and_generator::GameRng::new(7);
+        let target = AiTarget {
+            position: Vec2::new(0., 300.),
+            velocity: Vec2::new(150., 0.),
+        };
+
+        let perfect_aim = get_aim_direction(game_rng.generator(), Vec2::ZERO, &target, 300., 1.0, 30.);
+        assert_eq!(
+            perfect_aim,
+            math_and_generator::get_intercept_direction(Vec2::ZERO, target.position, target.velocity, 300.)
+        );
+        assert!(perfect_aim.x > 0.);
+
+        for _ in 0..100 {
+            let inaccurate_aim = get_aim_direction(game_rng.generator(), Vec2::ZERO, &target, 300., 0.0, 30.);
+            assert!(inaccurate_aim.angle_between(perfect_aim).abs() <= 30f32.to_radians() + 0.0001);
+        }
+    }
+
+    #[test]
+    fn ennemy_chases_the_player_it_sees() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut game_config = GameConfig::default();
+        // No new ennemy during the test
+        game_config.ennemy.maximum_number = 1;
+        let mut world = World::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(std::time::Duration::from_millis(100));
+        world.insert_resource(time);
+        world.init_resource::<scoreboard::ScoreAndInfo>();
+        world.init_resource::<Events<ShotFired>>();
+        world.insert_resource(math_and_generator::GameRng::new(7));
+        world.spawn((
+            player::Player::new(&game_config.player, &game_config.weapons),
+            Transform::from_xyz(0., 0., 0.),
+        ));
+        let runner_entity = world
+            .spawn((
+                ennemies::Ennemy::new(&game_config.ennemy, ennemies::EnnemyKind::Runner, Vec2::new(0., 1.), Vec2::new(200., 0.)),
+                Transform::from_xyz(200., 0., 0.),
+            ))
+            .id();
+        world.insert_resource(game_config);
+
+        world.run_system_once(ennemy_ai_system);
+
+        // The runner was going up, it now runs toward the player
+        let runner_translation = world.get::<Transform>(runner_entity).unwrap().translation;
+        assert!(runner_translation.y.abs() < 0.001);
+        assert!(world.get::<Transform>(runner_entity).unwrap().translation.x < 200.);
+    }
+
+    #[test]
+    fn no_spawn_during_a_boss_fight() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut game_config = GameConfig::default();
+        // An ennemy spawns at each tick
+        game_config.ennemy.spawn_factor = 1;
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.init_resource::<scoreboard::ScoreAndInfo>();
+        world.init_resource::<Events<ShotFired>>();
+        world.insert_resource(math_and_generator::GameRng::new(7));
+        world.spawn((
+            ennemies::Ennemy::new(&game_config.ennemy, ennemies::EnnemyKind::Boss, Vec2::new(0., 1.), Vec2::ZERO),
+            Transform::from_xyz(0., 0., 0.),
+        ));
+        world.insert_resource(game_config);
+
+        world.run_system_once(ennemy_ai_system);
+        assert_eq!(world.query::<&ennemies::Ennemy>().iter(&world).count(), 1);
+
+        let boss_entity = world.query::<(Entity, &ennemies::Ennemy)>().single(&world).0;
+        world.despawn(boss_entity);
+        world.run_system_once(ennemy_ai_system);
+        assert_eq!(world.query::<&ennemies::Ennemy>().iter(&world).count(), 1);
+    }
+
+    #[test]
+    fn spawn_factor_decrease_does_not_overflow() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut game_config = GameConfig::default();
+        game_config.ennemy.spawn_factor_decrease_per_level = u32::MAX;
+        let mut world = World::new();
+        world.insert_resource(math_and_generator::GameRng::new(7));
+        world.insert_resource(game_config);
+
+        world.run_system_once(
+            |mut commands: Commands, mut game_rng: ResMut<math_and_generator::GameRng>, game_config: Res<GameConfig>| {
+                ennemy_spawn_system(&mut commands, &mut game_rng, 3, &game_config);
+            },
+        );
+
+        // The spawn factor is at its minimum: an ennemy spawns at each tick
+        assert_eq!(world.query::<&ennemies::Ennemy>().iter(&world).count(), 1);
+    }
+
+    #[test]
+    fn walker_spawns_without_spawn_weight() {
+        let mut config = EnnemyConfig::default();
+        for archetype in [
+            &mut config.walker,
+            &mut config.runner,
+            &mut config.spitter,
+            &mut config.tank,
+            &mut config.exploder,
+        ] {
+            archetype.spawn_weights.clear();
+        }
+
+        let mut game_rng = math_and_generator::GameRng::new(7);
+        assert_eq!(roll_ennemy_kind(game_rng.generator(), &config, 2), ennemies::EnnemyKind::Walker);
+    }
 }
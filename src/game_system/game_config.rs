@@ -0,0 +1,996 @@
+use crate::game_entity::ennemies::{EnnemyKind, ENNEMY_KINDS};
+use crate::weapons::WeaponKind;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use std::fs;
+use std::time::SystemTime;
+
+/// Default path of the game configuration file
+pub static GAME_CONFIG_PATH: &str = "assets/config/game.ron";
+/// Period between two checks of the game configuration file, in seconds
+static GAME_CONFIG_CHECK_PERIOD: f32 = 1.0;
+
+/// Every tuning value of the gameplay, loaded from a RON file.
+/// A value missing from the file keeps its default value.
+///
+/// The values read at each gameplay tick (game area, spawns, difficulty) are applied as soon as the file is reloaded,
+/// the values of the characters (speed, health, weapons) are applied to the characters spawned after the reload.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct GameConfig {
+    pub game_area: GameAreaConfig,
+    pub player: PlayerConfig,
+    pub ennemy: EnnemyConfig,
+    pub weapons: WeaponsConfig,
+    pub pickups: PickupsConfig,
+    pub difficulty: DifficultyConfig,
+    pub boss: BossConfig,
+}
+
+/// Limits of the game area, the center of the area is (0, 0)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct GameAreaConfig {
+    pub limit_x: f32,
+    pub limit_y: f32,
+}
+
+/// The main character
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct PlayerConfig {
+    pub initial_position: Vec2,
+    pub initial_direction: Vec2,
+    pub speed: f32,
+    pub health: u32,
+    /// Health given back per second
+    pub health_regen: f32,
+    /// Damage absorbed by each hit, a hit always removes at least 1 health
+    pub armor: u32,
+    pub hitbox_size: Vec2,
+    /// The weapon of the player at the beginning of the game
+    pub weapon: WeaponKind,
+    /// Number of weapons the player can carry
+    pub inventory_size: usize,
+    /// Time before the player can fire after a weapon switch, in seconds
+    pub weapon_switch_delay: f32,
+    /// Time during which the player cannot be hurt again after a hit, in seconds
+    pub invulnerability_duration: f32,
+    /// Distance the player is pushed back when a zombie touches it
+    pub knockback_distance: f32,
+}
+
+/// The zombies, their spawn and the characteristics of each archetype
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct EnnemyConfig {
+    /// Distance from its spawn position before the ennemy turns back
+    pub maximum_distance: f32,
+    pub maximum_number: usize,
+    /// An ennemy spawns at a tick with a probability of 3 / (spawn_factor - spawn_factor_decrease_per_level * difficulty level)
+    pub spawn_factor: u32,
+    pub spawn_factor_decrease_per_level: u32,
+    /// Distance from the player under which the ennemies stop wandering to hunt the player
+    pub detection_radius: f32,
+    /// Precision of the shots of the ennemies at each difficulty level (from "EASY"), from 0 to 1 for a perfect leading shot.
+    /// The last value is kept for the next levels
+    pub aim_accuracy: Vec<f32>,
+    /// Largest deviation of a shot with an accuracy of 0, in degrees
+    pub maximum_aim_error: f32,
+    pub walker: EnnemyArchetypeConfig,
+    pub runner: EnnemyArchetypeConfig,
+    pub spitter: EnnemyArchetypeConfig,
+    pub tank: EnnemyArchetypeConfig,
+    pub exploder: EnnemyArchetypeConfig,
+    /// The boss of the boss fights, its spawn weights are ignored (see `BossConfig`)
+    pub boss: EnnemyArchetypeConfig,
+}
+
+impl EnnemyConfig {
+    /// Get the precision of the shots of the ennemies at a difficulty level, from 0 to 1
+    ///
+    /// # Arguments
+    ///
+    /// * `difficulty_level` - The current difficulty level
+    ///
+    pub fn get_aim_accuracy(&self, difficulty_level: u32) -> f32 {
+        get_value_of_level(&self.aim_accuracy, difficulty_level)
+            .unwrap_or(1.0)
+            .clamp(0.0, 1.0)
+    }
+
+    /// Get the characteristics of an archetype of ennemy
+    pub fn get(&self, kind: EnnemyKind) -> &EnnemyArchetypeConfig {
+        match kind {
+            EnnemyKind::Walker => &self.walker,
+            EnnemyKind::Runner => &self.runner,
+            EnnemyKind::Spitter => &self.spitter,
+            EnnemyKind::Tank => &self.tank,
+            EnnemyKind::Exploder => &self.exploder,
+            EnnemyKind::Boss => &self.boss,
+        }
+    }
+}
+
+/// The characteristics of an archetype of ennemy. Every value is required when an archetype is written in the file,
+/// except `weapon` and `death_explosion`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EnnemyArchetypeConfig {
+    pub speed: f32,
+    pub health: u32,
+    /// Health given back per second
+    pub health_regen: f32,
+    /// Damage absorbed by each hit, a hit always removes at least 1 health
+    pub armor: u32,
+    pub points_per_hit: u32,
+    /// Points on death = points_per_hit * death_point_coef
+    pub death_point_coef: u32,
+    pub hitbox_size: Vec2,
+    /// Health removed from the player when the ennemy touches it
+    pub contact_damage: u32,
+    /// Distance from the player under which an armed ennemy stops to fire at the player
+    pub attack_range: f32,
+    /// Distance from the player under which the ennemy runs away, 0 for an ennemy which never flees
+    pub flee_distance: f32,
+    /// The weapon of the ennemy, None for a melee only ennemy
+    #[serde(default)]
+    pub weapon: Option<WeaponConfig>,
+    /// The explosion of the ennemy when it dies, None when it does not explode
+    #[serde(default)]
+    pub death_explosion: Option<DeathExplosionConfig>,
+    /// Relative probability of a spawn of the archetype at each difficulty level (from "EASY").
+    /// The last value is kept for the next levels
+    pub spawn_weights: Vec<u32>,
+}
+
+impl EnnemyArchetypeConfig {
+    /// Get the relative probability of a spawn of the archetype at a difficulty level
+    ///
+    /// # Arguments
+    ///
+    /// * `difficulty_level` - The current difficulty level
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///     # use street_of_zombies::game_system::game_config::EnnemyConfig;
+    ///     let tank = &EnnemyConfig::default().tank;
+    ///     assert_eq!(tank.get_spawn_weight(0), 0);
+    /// ```
+    pub fn get_spawn_weight(&self, difficulty_level: u32) -> u32 {
+        get_value_of_level(&self.spawn_weights, difficulty_level).unwrap_or(0)
+    }
+}
+
+/// Get the value of a difficulty level in a list of values (one per level), the last value is kept for the next levels.
+/// None when the list is empty
+fn get_value_of_level<T: Copy>(values_per_level: &[T], difficulty_level: u32) -> Option<T> {
+    values_per_level
+        .get(difficulty_level as usize)
+        .or(values_per_level.last())
+        .copied()
+}
+
+/// The explosion of an ennemy when it dies, it hurts the player
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DeathExplosionConfig {
+    pub radius: f32,
+    /// Health removed from the player in the radius of the explosion
+    pub damage: u32,
+}
+
+/// The characteristics of a weapon. Every value is required when a weapon is written in the file,
+/// except the ones of the shotgun and of the rocket launcher, and the appearance of the projectiles
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WeaponConfig {
+    pub projectile_speed: f32,
+    /// Time between two shots, in seconds
+    pub fire_rate: f32,
+    pub magazine_size: u32,
+    /// Amo carried outside of the magazine at the beginning (and at most), None when unlimited
+    pub reserve_ammo: Option<u32>,
+    /// Duration of a reload, in seconds
+    pub reload_time: f32,
+    pub fire_distance: u32,
+    /// Health removed by a projectile
+    pub damage: u32,
+    /// Number of different ennemies a projectile hits before it disappears
+    pub pierce_count: u32,
+    /// Number of pellets of a single shot of the shotgun
+    #[serde(default = "get_single_pellet")]
+    pub pellets: u32,
+    /// Angle between the first and the last pellet of a shot of the shotgun, in radians
+    #[serde(default)]
+    pub spread_angle: f32,
+    /// Radius of the explosion of a rocket
+    #[serde(default)]
+    pub explosion_radius: f32,
+    /// Color and size of the projectiles, the default projectile when missing
+    #[serde(default)]
+    pub projectile_appearance: Option<ProjectileAppearanceConfig>,
+}
+
+/// The look of the projectiles of a weapon. The projectiles of the ennemies are always red
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ProjectileAppearanceConfig {
+    pub color: Color,
+    pub size: Vec2,
+}
+
+/// A weapon fires a single projectile per shot, unless its pellets are written in the file
+fn get_single_pellet() -> u32 {
+    1
+}
+
+/// The characteristics of each weapon of the player
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct WeaponsConfig {
+    pub pistol: WeaponConfig,
+    pub shotgun: WeaponConfig,
+    pub submachine_gun: WeaponConfig,
+    pub sniper_rifle: WeaponConfig,
+    pub rocket_launcher: WeaponConfig,
+}
+
+impl WeaponsConfig {
+    /// Get the characteristics of a type of weapon
+    pub fn get(&self, kind: WeaponKind) -> &WeaponConfig {
+        match kind {
+            WeaponKind::Pistol => &self.pistol,
+            WeaponKind::Shotgun => &self.shotgun,
+            WeaponKind::SubmachineGun => &self.submachine_gun,
+            WeaponKind::SniperRifle => &self.sniper_rifle,
+            WeaponKind::RocketLauncher => &self.rocket_launcher,
+        }
+    }
+}
+
+/// The bonus dropped by the killed ennemies
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct PickupsConfig {
+    /// Probability (from 0 to 1) that a killed ennemy drops a pickup
+    pub drop_chance: f32,
+    /// Time before a pickup disappears, in seconds
+    pub lifetime: f32,
+    pub hitbox_size: Vec2,
+    /// Relative probability of each pickup in the drop table
+    pub weapon_weight: u32,
+    pub ammo_refill_weight: u32,
+    pub health_weight: u32,
+    pub speed_boost_weight: u32,
+    pub health_restored: u32,
+    pub speed_boost_factor: f32,
+    /// Duration of the speed boost, in seconds
+    pub speed_boost_duration: f32,
+}
+
+/// The progression of the difficulty
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct DifficultyConfig {
+    pub seconds_before_next_level: u32,
+}
+
+/// The boss fights, started when a difficulty level is reached. The characteristics of the boss are in `ennemy.boss`.
+/// The regular spawns are paused until the boss is dead
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct BossConfig {
+    /// The difficulty levels which start a boss fight
+    pub difficulty_levels: Vec<u32>,
+    /// The archetype of the minions summoned by the boss
+    pub minion_kind: EnnemyKind,
+    /// Distance from the boss where its minions appear
+    pub summon_distance: f32,
+    pub burst_projectile_speed: f32,
+    pub burst_fire_distance: u32,
+    /// Health removed by a projectile of a radial burst
+    pub burst_damage: u32,
+    /// The phases of the fight, from the first one. Each phase has a lower `health_percent` than the previous one
+    pub phases: Vec<BossPhaseConfig>,
+}
+
+/// A phase of a boss fight. Every value is required when a phase is written in the file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BossPhaseConfig {
+    /// The phase starts once the health of the boss is at or under this percentage of its maximum health
+    pub health_percent: u32,
+    /// Number of projectiles of a radial burst, 0 without burst
+    pub burst_projectiles: u32,
+    /// Time between two radial bursts, in seconds
+    pub burst_interval: f32,
+    /// Number of minions summoned at once, 0 without summon
+    pub summoned_minions: u32,
+    /// Time between two summons, in seconds
+    pub summon_interval: f32,
+}
+
+impl Default for GameAreaConfig {
+    fn default() -> Self {
+        GameAreaConfig {
+            limit_x: 500.0,
+            limit_y: 300.0,
+        }
+    }
+}
+
+impl Default for PlayerConfig {
+    fn default() -> Self {
+        PlayerConfig {
+            initial_position: Vec2::new(0.0, -215.0),
+            initial_direction: Vec2::new(0.0, 1.0),
+            speed: 350.0,
+            health: 5,
+            health_regen: 0.0,
+            armor: 0,
+            hitbox_size: Vec2::new(35., 40.),
+            weapon: WeaponKind::Pistol,
+            inventory_size: 4,
+            weapon_switch_delay: 0.3,
+            invulnerability_duration: 1.0,
+            knockback_distance: 60.0,
+        }
+    }
+}
+
+impl Default for EnnemyConfig {
+    fn default() -> Self {
+        EnnemyConfig {
+            maximum_distance: 300.,
+            maximum_number: 40,
+            spawn_factor: 1100,
+            spawn_factor_decrease_per_level: 200,
+            detection_radius: 450.0,
+            aim_accuracy: vec![0.3, 0.5, 0.65, 0.8, 0.9, 1.0],
+            maximum_aim_error: 30.0,
+            walker: EnnemyArchetypeConfig {
+                speed: 200.0,
+                health: 3,
+                health_regen: 0.0,
+                armor: 0,
+                points_per_hit: 50,
+                death_point_coef: 4,
+                hitbox_size: Vec2::new(40., 50.),
+                contact_damage: 1,
+                attack_range: 350.0,
+                flee_distance: 0.0,
+                weapon: Some(WeaponConfig {
+                    projectile_speed: 300.0,
+                    fire_rate: 0.5,
+                    magazine_size: 3,
+                    reserve_ammo: None,
+                    reload_time: 2.5,
+                    fire_distance: 500,
+                    damage: 1,
+                    pierce_count: 1,
+                    pellets: 1,
+                    spread_angle: 0.0,
+                    explosion_radius: 0.0,
+                    projectile_appearance: None,
+                }),
+                death_explosion: None,
+                spawn_weights: vec![10, 8, 6, 5, 4, 4],
+            },
+            runner: EnnemyArchetypeConfig {
+                speed: 340.0,
+                health: 1,
+                health_regen: 0.0,
+                armor: 0,
+                points_per_hit: 40,
+                death_point_coef: 4,
+                hitbox_size: Vec2::new(35., 45.),
+                contact_damage: 1,
+                attack_range: 0.0,
+                flee_distance: 0.0,
+                weapon: None,
+                death_explosion: None,
+                spawn_weights: vec![0, 3, 4, 4, 5, 5],
+            },
+            spitter: EnnemyArchetypeConfig {
+                speed: 140.0,
+                health: 2,
+                health_regen: 0.0,
+                armor: 0,
+                points_per_hit: 60,
+                death_point_coef: 4,
+                hitbox_size: Vec2::new(40., 50.),
+                contact_damage: 1,
+                attack_range: 450.0,
+                flee_distance: 200.0,
+                weapon: Some(WeaponConfig {
+                    projectile_speed: 250.0,
+                    fire_rate: 1.2,
+                    magazine_size: 2,
+                    reserve_ammo: None,
+                    reload_time: 3.0,
+                    fire_distance: 750,
+                    damage: 1,
+                    pierce_count: 1,
+                    pellets: 1,
+                    spread_angle: 0.0,
+                    explosion_radius: 0.0,
+                    projectile_appearance: None,
+                }),
+                death_explosion: None,
+                spawn_weights: vec![2, 3, 3, 4, 4, 4],
+            },
+            tank: EnnemyArchetypeConfig {
+                speed: 110.0,
+                health: 10,
+                health_regen: 0.0,
+                armor: 1,
+                points_per_hit: 40,
+                death_point_coef: 6,
+                hitbox_size: Vec2::new(60., 75.),
+                contact_damage: 2,
+                attack_range: 0.0,
+                flee_distance: 0.0,
+                weapon: None,
+                death_explosion: None,
+                spawn_weights: vec![0, 1, 2, 3, 3, 4],
+            },
+            exploder: EnnemyArchetypeConfig {
+                speed: 260.0,
+                health: 2,
+                health_regen: 0.0,
+                armor: 0,
+                points_per_hit: 50,
+                death_point_coef: 3,
+                hitbox_size: Vec2::new(40., 50.),
+                contact_damage: 1,
+                attack_range: 0.0,
+                flee_distance: 0.0,
+                weapon: None,
+                death_explosion: Some(DeathExplosionConfig {
+                    radius: 100.0,
+                    damage: 2,
+                }),
+                spawn_weights: vec![0, 1, 2, 2, 3, 4],
+            },
+            boss: EnnemyArchetypeConfig {
+                speed: 90.0,
+                health: 60,
+                health_regen: 0.0,
+                armor: 0,
+                points_per_hit: 20,
+                death_point_coef: 250,
+                hitbox_size: Vec2::new(110., 140.),
+                contact_damage: 3,
+                attack_range: 0.0,
+                flee_distance: 0.0,
+                weapon: None,
+                death_explosion: None,
+                spawn_weights: vec![],
+            },
+        }
+    }
+}
+
+impl Default for WeaponsConfig {
+    fn default() -> Self {
+        WeaponsConfig {
+            pistol: WeaponConfig {
+                projectile_speed: 700.0,
+                fire_rate: 0.18,
+                magazine_size: 12,
+                reserve_ammo: Some(240),
+                reload_time: 1.0,
+                fire_distance: 700,
+                damage: 1,
+                pierce_count: 1,
+                pellets: 1,
+                spread_angle: 0.0,
+                explosion_radius: 0.0,
+                projectile_appearance: None,
+            },
+            shotgun: WeaponConfig {
+                projectile_speed: 600.0,
+                fire_rate: 0.8,
+                magazine_size: 6,
+                reserve_ammo: Some(36),
+                reload_time: 1.5,
+                fire_distance: 350,
+                damage: 1,
+                pierce_count: 1,
+                pellets: 6,
+                spread_angle: 0.5,
+                explosion_radius: 0.0,
+                projectile_appearance: Some(ProjectileAppearanceConfig {
+                    color: Color::rgb(1.0, 0.6, 0.2),
+                    size: Vec2::new(4., 4.),
+                }),
+            },
+            submachine_gun: WeaponConfig {
+                projectile_speed: 800.0,
+                fire_rate: 0.07,
+                magazine_size: 30,
+                reserve_ammo: Some(240),
+                reload_time: 1.8,
+                fire_distance: 400,
+                damage: 1,
+                pierce_count: 1,
+                pellets: 1,
+                spread_angle: 0.0,
+                explosion_radius: 0.0,
+                projectile_appearance: Some(ProjectileAppearanceConfig {
+                    color: Color::rgb(1.0, 1.0, 0.3),
+                    size: Vec2::new(3., 3.),
+                }),
+            },
+            sniper_rifle: WeaponConfig {
+                projectile_speed: 1400.0,
+                fire_rate: 1.0,
+                magazine_size: 5,
+                reserve_ammo: Some(20),
+                reload_time: 2.0,
+                fire_distance: 1200,
+                damage: 1,
+                pierce_count: 3,
+                pellets: 1,
+                spread_angle: 0.0,
+                explosion_radius: 0.0,
+                projectile_appearance: Some(ProjectileAppearanceConfig {
+                    color: Color::rgb(0.3, 1.0, 1.0),
+                    size: Vec2::new(6., 6.),
+                }),
+            },
+            rocket_launcher: WeaponConfig {
+                projectile_speed: 450.0,
+                fire_rate: 1.5,
+                magazine_size: 1,
+                reserve_ammo: Some(8),
+                reload_time: 2.5,
+                fire_distance: 900,
+                damage: 1,
+                pierce_count: 1,
+                pellets: 1,
+                spread_angle: 0.0,
+                explosion_radius: 120.0,
+                projectile_appearance: Some(ProjectileAppearanceConfig {
+                    color: Color::rgb(1.0, 0.3, 0.0),
+                    size: Vec2::new(12., 12.),
+                }),
+            },
+        }
+    }
+}
+
+impl Default for PickupsConfig {
+    fn default() -> Self {
+        PickupsConfig {
+            drop_chance: 0.15,
+            lifetime: 10.0,
+            hitbox_size: Vec2::new(20., 20.),
+            weapon_weight: 3,
+            ammo_refill_weight: 3,
+            health_weight: 2,
+            speed_boost_weight: 2,
+            health_restored: 1,
+            speed_boost_factor: 1.5,
+            speed_boost_duration: 8.0,
+        }
+    }
+}
+
+impl Default for DifficultyConfig {
+    fn default() -> Self {
+        DifficultyConfig {
+            seconds_before_next_level: 30,
+        }
+    }
+}
+
+impl Default for BossConfig {
+    fn default() -> Self {
+        BossConfig {
+            difficulty_levels: vec![2, 4],
+            minion_kind: EnnemyKind::Runner,
+            summon_distance: 120.0,
+            burst_projectile_speed: 280.0,
+            burst_fire_distance: 800,
+            burst_damage: 1,
+            phases: vec![
+                BossPhaseConfig {
+                    health_percent: 100,
+                    burst_projectiles: 8,
+                    burst_interval: 2.5,
+                    summoned_minions: 0,
+                    summon_interval: 0.0,
+                },
+                BossPhaseConfig {
+                    health_percent: 60,
+                    burst_projectiles: 12,
+                    burst_interval: 2.0,
+                    summoned_minions: 2,
+                    summon_interval: 8.0,
+                },
+                BossPhaseConfig {
+                    health_percent: 30,
+                    burst_projectiles: 16,
+                    burst_interval: 1.2,
+                    summoned_minions: 3,
+                    summon_interval: 6.0,
+                },
+            ],
+        }
+    }
+}
+
+impl GameConfig {
+    /// Read a game configuration from the content of a RON file, the values which would break the game are rejected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///     # use street_of_zombies::game_system::game_config::GameConfig;
+    ///     let config = GameConfig::from_ron("(player: (speed: 500.0))").unwrap();
+    ///     assert_eq!(config.player.speed, 500.0);
+    /// ```
+    pub fn from_ron(content: &str) -> Result<Self, String> {
+        let config: GameConfig =
+            ron::from_str(content).map_err(|error| format!("Invalid game configuration: {}", error))?;
+
+        config
+            .validate()
+            .map_err(|error| format!("Invalid game configuration: {}", error))?;
+        Ok(config)
+    }
+
+    /// Check the values which cannot be used by the gameplay systems (negative limits of the game area,
+    /// probabilities out of 0 to 1, empty lists, empty hitboxes and magazines, drop tables and spawns without any weight)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///     # use street_of_zombies::game_system::game_config::GameConfig;
+    ///     assert_eq!(GameConfig::default().validate(), Ok(()));
+    /// ```
+    pub fn validate(&self) -> Result<(), String> {
+        // The comparisons are written to reject NaN too
+        if !(self.game_area.limit_x >= 0. && self.game_area.limit_y >= 0.) {
+            return Err("the limits of the game area cannot be negative".to_string());
+        }
+        if !(0. ..=1.).contains(&self.pickups.drop_chance) {
+            return Err("pickups.drop_chance must be from 0 to 1".to_string());
+        }
+        if self.ennemy.aim_accuracy.is_empty() {
+            return Err("ennemy.aim_accuracy needs a value for the first difficulty level".to_string());
+        }
+        if self.boss.phases.is_empty() {
+            return Err("boss.phases needs at least one phase".to_string());
+        }
+        if self.player.inventory_size == 0 {
+            return Err("player.inventory_size cannot be 0".to_string());
+        }
+        if !is_valid_hitbox_size(self.player.hitbox_size) {
+            return Err("player.hitbox_size must be greater than 0".to_string());
+        }
+        if !is_valid_hitbox_size(self.pickups.hitbox_size) {
+            return Err("pickups.hitbox_size must be greater than 0".to_string());
+        }
+        if self.ennemy.spawn_factor == 0 {
+            return Err("ennemy.spawn_factor cannot be 0".to_string());
+        }
+
+        let pickups = &self.pickups;
+        if pickups.weapon_weight + pickups.ammo_refill_weight + pickups.health_weight + pickups.speed_boost_weight == 0 {
+            return Err("the pickups need at least one weight greater than 0".to_string());
+        }
+
+        let archetypes = [
+            ("walker", &self.ennemy.walker),
+            ("runner", &self.ennemy.runner),
+            ("spitter", &self.ennemy.spitter),
+            ("tank", &self.ennemy.tank),
+            ("exploder", &self.ennemy.exploder),
+            ("boss", &self.ennemy.boss),
+        ];
+        for (name, archetype) in archetypes {
+            if !is_valid_hitbox_size(archetype.hitbox_size) {
+                return Err(format!("ennemy.{}.hitbox_size must be greater than 0", name));
+            }
+            if let Some(weapon) = &archetype.weapon {
+                validate_weapon(weapon).map_err(|error| format!("ennemy.{}.weapon.{}", name, error))?;
+            }
+        }
+
+        // The last spawn weight of each archetype is kept for the next levels
+        let number_of_levels = ENNEMY_KINDS
+            .iter()
+            .map(|kind| self.ennemy.get(*kind).spawn_weights.len())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        for difficulty_level in 0..number_of_levels as u32 {
+            let total_weight: u32 = ENNEMY_KINDS
+                .iter()
+                .map(|kind| self.ennemy.get(*kind).get_spawn_weight(difficulty_level))
+                .sum();
+            if total_weight == 0 {
+                return Err(format!("no ennemy can spawn at the difficulty level {}", difficulty_level));
+            }
+        }
+
+        let weapons = [
+            ("pistol", &self.weapons.pistol),
+            ("shotgun", &self.weapons.shotgun),
+            ("submachine_gun", &self.weapons.submachine_gun),
+            ("sniper_rifle", &self.weapons.sniper_rifle),
+            ("rocket_launcher", &self.weapons.rocket_launcher),
+        ];
+        for (name, weapon) in weapons {
+            validate_weapon(weapon).map_err(|error| format!("weapons.{}.{}", name, error))?;
+        }
+
+        Ok(())
+    }
+
+    /// Load a game configuration file
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content =
+            fs::read_to_string(path).map_err(|error| format!("Cannot read {}: {}", path, error))?;
+        GameConfig::from_ron(&content).map_err(|error| format!("{}: {}", path, error))
+    }
+
+    /// Load the game configuration file, or the default configuration when the file does not exist
+    pub fn load_or_default(path: &str) -> Result<Self, String> {
+        if fs::metadata(path).is_ok() {
+            GameConfig::load(path)
+        } else {
+            Ok(GameConfig::default())
+        }
+    }
+}
+
+/// Check the values of a weapon which cannot be used by the gameplay systems, the error starts with the name of the value
+fn validate_weapon(weapon: &WeaponConfig) -> Result<(), String> {
+    if weapon.magazine_size == 0 {
+        return Err("magazine_size cannot be 0".to_string());
+    }
+    if weapon.fire_rate.is_nan() || weapon.fire_rate < 0. {
+        return Err("fire_rate cannot be negative".to_string());
+    }
+    if weapon.reload_time.is_nan() || weapon.reload_time < 0. {
+        return Err("reload_time cannot be negative".to_string());
+    }
+    if let Some(appearance) = weapon.projectile_appearance {
+        if !is_valid_hitbox_size(appearance.size) {
+            return Err("projectile_appearance.size must be greater than 0".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// A hitbox needs a width and a height (NaN is rejected too)
+fn is_valid_hitbox_size(hitbox_size: Vec2) -> bool {
+    hitbox_size.x > 0. && hitbox_size.y > 0.
+}
+
+/// Watch the game configuration file, the `GameConfig` resource is reloaded when the file changes
+#[derive(Resource)]
+pub struct GameConfigWatcher {
+    path: String,
+    last_modification: Option<SystemTime>,
+    check_timer: Timer,
+}
+
+impl GameConfigWatcher {
+    /// Returns a new GameConfigWatcher
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the game configuration file to watch
+    ///
+    pub fn new(path: String) -> Self {
+        let last_modification = get_modification_time(&path);
+
+        GameConfigWatcher {
+            path,
+            last_modification,
+            check_timer: Timer::from_seconds(GAME_CONFIG_CHECK_PERIOD, TimerMode::Repeating),
+        }
+    }
+}
+
+fn get_modification_time(path: &str) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Reload the game configuration when its file is modified. Managed by as a "Bevy System"
+pub fn hot_reload_game_config_system(
+    time: Res<Time<Real>>,
+    mut watcher: ResMut<GameConfigWatcher>,
+    mut game_config: ResMut<GameConfig>,
+) {
+    if !watcher.check_timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let last_modification = get_modification_time(&watcher.path);
+    if last_modification == watcher.last_modification {
+        return;
+    }
+    watcher.last_modification = last_modification;
+
+    // An invalid file keeps the previous configuration, the designer can fix it while the game runs
+    match GameConfig::load(&watcher.path) {
+        Ok(new_config) => {
+            info!("Game configuration reloaded from {}", watcher.path);
+            *game_config = new_config;
+        }
+        Err(error) => error!("{}", error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spawn_weight_of_the_last_level_is_kept() {
+        let runner = &EnnemyConfig::default().runner;
+
+        assert_eq!(runner.get_spawn_weight(0), 0);
+        assert_eq!(runner.get_spawn_weight(1), 3);
+        assert_eq!(runner.get_spawn_weight(50), *runner.spawn_weights.last().unwrap());
+    }
+
+    #[test]
+    fn shipped_config_file_matches_the_defaults() {
+        assert_eq!(GameConfig::load(GAME_CONFIG_PATH), Ok(GameConfig::default()));
+    }
+
+    #[test]
+    fn only_the_shotgun_and_the_rocket_launcher_need_their_special_values() {
+        let weapons = GameConfig::load(GAME_CONFIG_PATH).unwrap().weapons;
+
+        assert_eq!((weapons.shotgun.pellets, weapons.shotgun.spread_angle), (6, 0.5));
+        assert_eq!(weapons.rocket_launcher.explosion_radius, 120.0);
+        assert_eq!((weapons.pistol.pellets, weapons.pistol.spread_angle), (1, 0.0));
+        assert_eq!(weapons.pistol.explosion_radius, 0.0);
+    }
+
+    #[test]
+    fn missing_values_keep_their_defaults() {
+        let config = GameConfig::from_ron("(ennemy: (maximum_number: 10))").unwrap();
+
+        assert_eq!(config.ennemy.maximum_number, 10);
+        assert_eq!(config.ennemy.walker, EnnemyConfig::default().walker);
+        assert_eq!(config.ennemy.exploder, EnnemyConfig::default().exploder);
+        assert_eq!(config.player, PlayerConfig::default());
+    }
+
+    #[test]
+    fn invalid_config() {
+        assert!(GameConfig::from_ron("(player: (speed: \"fast\"))").is_err());
+        assert!(GameConfig::from_ron("(weapons: (shotgun: (magazine_size: 10)))").is_err());
+        assert!(GameConfig::from_ron("(player: (weapon: Bazooka))").is_err());
+        assert!(GameConfig::from_ron("(ennemy: (tank: (speed: 50.0)))").is_err());
+    }
+
+    #[test]
+    fn values_breaking_the_game_are_rejected() {
+        assert!(GameConfig::from_ron("(game_area: (limit_x: -500.0))").is_err());
+        assert!(GameConfig::from_ron("(game_area: (limit_y: NaN))").is_err());
+        assert!(GameConfig::from_ron("(pickups: (drop_chance: NaN))").is_err());
+        assert!(GameConfig::from_ron("(pickups: (drop_chance: 1.5))").is_err());
+        assert!(GameConfig::from_ron("(ennemy: (aim_accuracy: []))").is_err());
+        assert!(GameConfig::from_ron("(boss: (phases: []))").is_err());
+        assert!(GameConfig::from_ron("(game_area: (limit_x: 0.0), pickups: (drop_chance: 1.0))").is_ok());
+    }
+
+    #[test]
+    fn empty_magazines_are_rejected() {
+        assert!(GameConfig::from_ron("(weapons: (pistol: (projectile_speed: 500.0, fire_rate: 0.2, magazine_size: 0, reserve_ammo: None, reload_time: 1.0, fire_distance: 500, damage: 1, pierce_count: 0)))").is_err());
+
+        let mut config = GameConfig::default();
+        config.ennemy.spitter.weapon.as_mut().unwrap().magazine_size = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn negative_fire_rates_and_reload_times_are_rejected() {
+        for fire_rate in [-0.1, f32::NAN] {
+            let mut config = GameConfig::default();
+            config.weapons.submachine_gun.fire_rate = fire_rate;
+            assert!(config.validate().is_err());
+        }
+        for reload_time in [-1.0, f32::NAN] {
+            let mut config = GameConfig::default();
+            config.weapons.sniper_rifle.reload_time = reload_time;
+            assert!(config.validate().is_err());
+        }
+
+        let mut config = GameConfig::default();
+        config.weapons.pistol.fire_rate = 0.;
+        config.weapons.pistol.reload_time = 0.;
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn empty_inventory_is_rejected() {
+        assert!(GameConfig::from_ron("(player: (inventory_size: 0))").is_err());
+        assert!(GameConfig::from_ron("(player: (inventory_size: 1))").is_ok());
+    }
+
+    #[test]
+    fn empty_hitboxes_are_rejected() {
+        assert!(GameConfig::from_ron("(player: (hitbox_size: (0.0, 10.0)))").is_err());
+        assert!(GameConfig::from_ron("(pickups: (hitbox_size: (10.0, -5.0)))").is_err());
+        assert!(GameConfig::from_ron("(player: (hitbox_size: (NaN, 10.0)))").is_err());
+
+        let mut config = GameConfig::default();
+        config.ennemy.tank.hitbox_size = Vec2::new(80., 0.);
+        assert!(config.validate().is_err());
+
+        let mut config = GameConfig::default();
+        config.weapons.sniper_rifle.projectile_appearance.as_mut().unwrap().size = Vec2::new(6., 0.);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn drop_table_and_spawns_without_weight_are_rejected() {
+        assert!(GameConfig::from_ron(
+            "(pickups: (weapon_weight: 0, ammo_refill_weight: 0, health_weight: 0, speed_boost_weight: 0))"
+        )
+        .is_err());
+        assert!(GameConfig::from_ron("(pickups: (weapon_weight: 0, ammo_refill_weight: 0, health_weight: 1, speed_boost_weight: 0))").is_ok());
+
+        // Only the walkers and the spitters spawn at the first level
+        let mut config = GameConfig::default();
+        config.ennemy.walker.spawn_weights = vec![0, 5];
+        config.ennemy.spitter.spawn_weights = vec![0, 5];
+        assert!(config.validate().is_err());
+
+        // The last weight of each archetype is kept for the next levels
+        let mut config = GameConfig::default();
+        config.ennemy.walker.spawn_weights = vec![1, 0];
+        config.ennemy.spitter.spawn_weights = vec![1, 0];
+        for archetype in [&mut config.ennemy.runner, &mut config.ennemy.tank, &mut config.ennemy.exploder] {
+            archetype.spawn_weights = vec![0];
+        }
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn null_spawn_factor_is_rejected() {
+        assert!(GameConfig::from_ron("(ennemy: (spawn_factor: 0))").is_err());
+        assert!(GameConfig::from_ron("(ennemy: (spawn_factor: 1))").is_ok());
+    }
+
+    #[test]
+    fn invalid_reloaded_file_keeps_the_previous_config() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let path = std::env::temp_dir().join(format!("street_of_zombies_{}.ron", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+        fs::write(&path, "(game_area: (limit_x: 800.0))").unwrap();
+
+        let mut world = World::new();
+        world.init_resource::<Time<Real>>();
+        world.insert_resource(GameConfig::load(&path).unwrap());
+        // The watcher does not know the file yet, the next check reloads it
+        world.insert_resource(GameConfigWatcher::new(String::new()));
+        world.resource_mut::<GameConfigWatcher>().path = path.clone();
+        fs::write(&path, "(game_area: (limit_x: -800.0))").unwrap();
+
+        // The first update of the real time has no delta
+        for _ in 0..2 {
+            world
+                .resource_mut::<Time<Real>>()
+                .update_with_duration(std::time::Duration::from_secs_f32(GAME_CONFIG_CHECK_PERIOD));
+        }
+        world.run_system_once(hot_reload_game_config_system);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(world.resource::<GameConfig>().game_area.limit_x, 800.0);
+        assert!(world.resource::<GameConfigWatcher>().last_modification.is_some());
+    }
+}
@@ -0,0 +1,134 @@
+use crate::game_system::input_bindings::{InputAction, InputBindings};
+use crate::game_system::player_input::{self, PlayerInput};
+use crate::game_system::replay::ReplayPlayer;
+use crate::game_system::scoreboard::ScoreAndInfo;
+
+use bevy::input::gamepad::GamepadConnectionEvent;
+use bevy::prelude::*;
+
+/// The screens of the game
+#[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameState {
+    /// Title screen, waiting for the player
+    #[default]
+    MainMenu,
+    /// The gameplay runs
+    Playing,
+    /// The gameplay (projectiles, ennemies, difficulty timer) is frozen
+    Paused,
+    /// The player is dead, waiting for a restart
+    GameOver,
+    /// The rebind screen of the keys, opened from the main menu
+    Controls,
+}
+
+/// Run condition of the gameplay systems: the game is played and the player is still alive.
+///
+/// The game over is checked at each tick, the ticks following the death of the player do nothing
+/// even if the "GameOver" state is applied at the next frame.
+pub fn is_gameplay_running(state: Res<State<GameState>>, score: Res<ScoreAndInfo>) -> bool {
+    *state.get() == GameState::Playing && !score.is_gameover()
+}
+
+/// Change the state of the game following the actions bound to the keyboard (start, pause, restart, open the
+/// rebind screen from the main menu) or the Start button of a gamepad (play, pause, restart). Managed by as a "Bevy System"
+#[allow(clippy::too_many_arguments)]
+pub fn state_input_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    input_bindings: Res<InputBindings>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut player_input: ResMut<PlayerInput>,
+) {
+    let is_start_pressed =
+        player_input::is_gamepad_button_just_pressed(&gamepads, &gamepad_buttons, GamepadButtonType::Start);
+    let is_pause_pressed =
+        input_bindings.just_pressed(&keyboard_input, InputAction::Pause) || is_start_pressed;
+
+    match state.get() {
+        GameState::MainMenu
+            if input_bindings.just_pressed(&keyboard_input, InputAction::StartGame) || is_start_pressed =>
+        {
+            next_state.set(GameState::Playing)
+        }
+        GameState::MainMenu if input_bindings.just_pressed(&keyboard_input, InputAction::OpenControls) => {
+            next_state.set(GameState::Controls)
+        }
+        GameState::Playing if is_pause_pressed => next_state.set(GameState::Paused),
+        GameState::Paused if is_pause_pressed => next_state.set(GameState::Playing),
+        GameState::GameOver if player_input.restart => {
+            player_input.restart = false;
+            next_state.set(GameState::Playing);
+        }
+        _ => (),
+    }
+}
+
+/// Remembers that the game was paused by the disconnection of a gamepad
+#[derive(Resource, Default)]
+pub struct GamepadDisconnectionPause {
+    is_paused_by_disconnection: bool,
+}
+
+/// Log the connection of the gamepads. The game is paused when a gamepad is disconnected during the game,
+/// and resumed once a gamepad is plugged back (unless the player resumed it in the meantime). Managed by as a "Bevy System"
+pub fn gamepad_connection_system(
+    mut connection_events: EventReader<GamepadConnectionEvent>,
+    mut disconnection_pause: ResMut<GamepadDisconnectionPause>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    // The player already resumed the game
+    if *state.get() != GameState::Paused {
+        disconnection_pause.is_paused_by_disconnection = false;
+    }
+
+    for connection_event in connection_events.read() {
+        if connection_event.connected() {
+            info!("Gamepad {} connected", connection_event.gamepad.id);
+            if disconnection_pause.is_paused_by_disconnection {
+                disconnection_pause.is_paused_by_disconnection = false;
+                next_state.set(GameState::Playing);
+            }
+        } else {
+            info!("Gamepad {} disconnected", connection_event.gamepad.id);
+            if *state.get() == GameState::Playing {
+                disconnection_pause.is_paused_by_disconnection = true;
+                next_state.set(GameState::Paused);
+            }
+        }
+    }
+}
+
+/// A replay starts without waiting on the main menu, and restarts after each game over until its end.
+/// Managed by as a "Bevy System"
+pub fn replay_state_system(
+    replay_player: Res<ReplayPlayer>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let is_waiting_for_player = matches!(state.get(), GameState::MainMenu | GameState::GameOver);
+
+    if is_waiting_for_player && !replay_player.is_finished() {
+        next_state.set(GameState::Playing);
+    }
+}
+
+/// Game System: Go to the "GameOver" screen once the player is dead. Managed by as a "Bevy System"
+pub fn game_over_system(score: Res<ScoreAndInfo>, mut next_state: ResMut<NextState<GameState>>) {
+    if score.is_gameover() {
+        next_state.set(GameState::GameOver);
+    }
+}
+
+/// Freeze the game time (projectiles, ennemies and difficulty timer) when the game is paused
+pub fn pause_game(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+/// Restart the game time when the game is resumed
+pub fn resume_game(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
@@ -0,0 +1,48 @@
+use crate::game_entity::ennemies::EnnemyKind;
+
+use bevy::prelude::*;
+
+/// Sent when a projectile of the player (or its explosion) hurts an ennemy
+#[derive(Event)]
+pub struct EnnemyHit {
+    /// The ennemy hurt
+    pub entity: Entity,
+    /// The points given by the hit
+    pub points: u32,
+}
+
+/// Sent when an ennemy dies. The ennemy is despawned at the end of the tick
+#[derive(Event)]
+pub struct EnnemyKilled {
+    /// The archetype of the ennemy
+    pub kind: EnnemyKind,
+    /// The position of the ennemy when it died
+    pub position: Vec2,
+    /// The points given by the death
+    pub points: u32,
+}
+
+/// Sent when the player loses health, hit by a projectile or by an ennemy
+#[derive(Event)]
+pub struct PlayerHit {
+    /// The health removed, after the armor
+    pub damage: u32,
+}
+
+/// Sent when the player dies, the game is over
+#[derive(Event)]
+pub struct PlayerDied;
+
+/// Sent when the difficulty level increases
+#[derive(Event)]
+pub struct DifficultyIncreased {
+    /// The new difficulty level
+    pub difficulty_level: u32,
+}
+
+/// Sent for each shot of a weapon (a single event for all the projectiles of a shotgun shot)
+#[derive(Event)]
+pub struct ShotFired {
+    /// True if an ennemy fired, false for the player
+    pub is_from_ennemy: bool,
+}
@@ -0,0 +1,101 @@
+use crate::game_entity::health::Health;
+use crate::game_entity::*;
+use crate::game_system::gameplay_events::{EnnemyKilled, PlayerDied};
+
+use bevy::prelude::*;
+
+/// Game System: The characters regenerate their health. Managed by as a "Bevy System"
+pub fn health_regeneration_system(time: Res<Time>, mut health_query: Query<&mut Health>) {
+    for mut health in health_query.iter_mut() {
+        health.regenerate(time.delta_seconds());
+    }
+}
+
+/// Game System: Despawn the dead characters, then send `EnnemyKilled` for each ennemy and `PlayerDied` for the player.
+/// The score and the game over are treated by the listeners. Managed by as a "Bevy System"
+pub fn death_system(
+    mut commands: Commands,
+    health_query: Query<(Entity, &Health, &Transform, Option<&ennemies::Ennemy>)>,
+    mut killed_events: EventWriter<EnnemyKilled>,
+    mut player_died_events: EventWriter<PlayerDied>,
+) {
+    for (entity, health, transform, ennemy) in health_query.iter() {
+        if !health.is_dead() {
+            continue;
+        }
+
+        commands.entity(entity).despawn();
+        match ennemy {
+            Some(ennemy) => killed_events.send(EnnemyKilled {
+                kind: ennemy.get_kind(),
+                position: get_position(transform),
+                points: ennemy.get_point_value_on_death(),
+            }),
+            None => player_died_events.send(PlayerDied),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_system::game_config::{EnnemyConfig, PlayerConfig, WeaponsConfig};
+    use bevy::ecs::system::RunSystemOnce;
+
+    fn init_death_events(world: &mut World) {
+        world.init_resource::<Events<EnnemyKilled>>();
+        world.init_resource::<Events<PlayerDied>>();
+    }
+
+    #[test]
+    fn dead_characters_are_despawned() {
+        let mut world = World::new();
+        init_death_events(&mut world);
+
+        let mut dead_health = Health::new(1);
+        dead_health.take_damage(1);
+        world.spawn((
+            ennemies::Ennemy::new(&EnnemyConfig::default(), ennemies::EnnemyKind::Walker, Vec2::new(0., 1.), Vec2::ZERO),
+            dead_health,
+            Transform::from_xyz(10., 20., 0.),
+        ));
+        world.spawn((
+            player::Player::new(&PlayerConfig::default(), &WeaponsConfig::default()),
+            Health::new(5),
+            Transform::from_xyz(0., 0., 0.),
+        ));
+
+        world.run_system_once(death_system);
+
+        let ennemy_config = EnnemyConfig::default();
+        let killed_events = world.resource::<Events<EnnemyKilled>>();
+        let killed_ennemy = killed_events.iter_current_update_events().next().unwrap();
+        assert_eq!(killed_ennemy.kind, ennemies::EnnemyKind::Walker);
+        assert_eq!(killed_ennemy.position, Vec2::new(10., 20.));
+        assert_eq!(killed_ennemy.points, ennemy_config.walker.points_per_hit * ennemy_config.walker.death_point_coef);
+        assert!(world.resource::<Events<PlayerDied>>().is_empty());
+        assert_eq!(world.query::<&Health>().iter(&world).count(), 1);
+    }
+
+    #[test]
+    fn death_of_the_player_is_sent() {
+        let mut world = World::new();
+        init_death_events(&mut world);
+
+        let mut health = Health::new(2).with_armor(1);
+        health.take_damage(5);
+        let player_entity = world
+            .spawn((
+                player::Player::new(&PlayerConfig::default(), &WeaponsConfig::default()),
+                health,
+                Transform::from_xyz(0., 0., 0.),
+            ))
+            .id();
+
+        world.run_system_once(death_system);
+
+        assert_eq!(world.resource::<Events<PlayerDied>>().len(), 1);
+        assert!(world.resource::<Events<EnnemyKilled>>().is_empty());
+        assert!(world.get_entity(player_entity).is_none());
+    }
+}
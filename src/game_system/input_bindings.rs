@@ -0,0 +1,361 @@
+use crate::game_system::game_state::GameState;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::fs;
+
+/// Default path of the settings file of the player
+pub static SETTINGS_PATH: &str = "settings.ron";
+
+/// The actions of the player, each one is bound to one or several keys
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InputAction {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Fire,
+    Reload,
+    NextWeapon,
+    PreviousWeapon,
+    ChangeAimMode,
+    WeaponSlot1,
+    WeaponSlot2,
+    WeaponSlot3,
+    WeaponSlot4,
+    WeaponSlot5,
+    WeaponSlot6,
+    WeaponSlot7,
+    WeaponSlot8,
+    WeaponSlot9,
+    StartGame,
+    Pause,
+    Restart,
+    OpenControls,
+}
+
+impl InputAction {
+    /// Every action, in the order of the rebind screen
+    pub const ALL: [InputAction; 22] = [
+        InputAction::MoveUp,
+        InputAction::MoveDown,
+        InputAction::MoveLeft,
+        InputAction::MoveRight,
+        InputAction::Fire,
+        InputAction::Reload,
+        InputAction::NextWeapon,
+        InputAction::PreviousWeapon,
+        InputAction::ChangeAimMode,
+        InputAction::WeaponSlot1,
+        InputAction::WeaponSlot2,
+        InputAction::WeaponSlot3,
+        InputAction::WeaponSlot4,
+        InputAction::WeaponSlot5,
+        InputAction::WeaponSlot6,
+        InputAction::WeaponSlot7,
+        InputAction::WeaponSlot8,
+        InputAction::WeaponSlot9,
+        InputAction::StartGame,
+        InputAction::Pause,
+        InputAction::Restart,
+        InputAction::OpenControls,
+    ];
+
+    /// The actions selecting an inventory slot, from the first slot
+    pub const WEAPON_SLOTS: [InputAction; 9] = [
+        InputAction::WeaponSlot1,
+        InputAction::WeaponSlot2,
+        InputAction::WeaponSlot3,
+        InputAction::WeaponSlot4,
+        InputAction::WeaponSlot5,
+        InputAction::WeaponSlot6,
+        InputAction::WeaponSlot7,
+        InputAction::WeaponSlot8,
+        InputAction::WeaponSlot9,
+    ];
+
+    /// Get the name of the action, printed on the rebind screen
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            InputAction::MoveUp => "MOVE UP",
+            InputAction::MoveDown => "MOVE DOWN",
+            InputAction::MoveLeft => "MOVE LEFT",
+            InputAction::MoveRight => "MOVE RIGHT",
+            InputAction::Fire => "FIRE",
+            InputAction::Reload => "RELOAD",
+            InputAction::NextWeapon => "NEXT WEAPON",
+            InputAction::PreviousWeapon => "PREVIOUS WEAPON",
+            InputAction::ChangeAimMode => "CHANGE AIM MODE",
+            InputAction::WeaponSlot1 => "WEAPON SLOT 1",
+            InputAction::WeaponSlot2 => "WEAPON SLOT 2",
+            InputAction::WeaponSlot3 => "WEAPON SLOT 3",
+            InputAction::WeaponSlot4 => "WEAPON SLOT 4",
+            InputAction::WeaponSlot5 => "WEAPON SLOT 5",
+            InputAction::WeaponSlot6 => "WEAPON SLOT 6",
+            InputAction::WeaponSlot7 => "WEAPON SLOT 7",
+            InputAction::WeaponSlot8 => "WEAPON SLOT 8",
+            InputAction::WeaponSlot9 => "WEAPON SLOT 9",
+            InputAction::StartGame => "START GAME",
+            InputAction::Pause => "PAUSE",
+            InputAction::Restart => "RESTART",
+            InputAction::OpenControls => "OPEN CONTROLS",
+        }
+    }
+
+    /// Get the keys bound to the action when the settings file does not change them
+    fn get_default_keys(&self) -> Vec<KeyCode> {
+        match self {
+            InputAction::MoveUp => vec![KeyCode::Up, KeyCode::W],
+            InputAction::MoveDown => vec![KeyCode::Down, KeyCode::S],
+            InputAction::MoveLeft => vec![KeyCode::Left, KeyCode::A],
+            InputAction::MoveRight => vec![KeyCode::Right, KeyCode::D],
+            InputAction::Fire => vec![KeyCode::Space],
+            InputAction::Reload => vec![KeyCode::ShiftLeft],
+            InputAction::NextWeapon => vec![KeyCode::E],
+            InputAction::PreviousWeapon => vec![KeyCode::Q],
+            InputAction::ChangeAimMode => vec![KeyCode::M],
+            InputAction::WeaponSlot1 => vec![KeyCode::Key1],
+            InputAction::WeaponSlot2 => vec![KeyCode::Key2],
+            InputAction::WeaponSlot3 => vec![KeyCode::Key3],
+            InputAction::WeaponSlot4 => vec![KeyCode::Key4],
+            InputAction::WeaponSlot5 => vec![KeyCode::Key5],
+            InputAction::WeaponSlot6 => vec![KeyCode::Key6],
+            InputAction::WeaponSlot7 => vec![KeyCode::Key7],
+            InputAction::WeaponSlot8 => vec![KeyCode::Key8],
+            InputAction::WeaponSlot9 => vec![KeyCode::Key9],
+            InputAction::StartGame => vec![KeyCode::Return],
+            InputAction::Pause => vec![KeyCode::P, KeyCode::Escape],
+            InputAction::Restart => vec![KeyCode::R],
+            InputAction::OpenControls => vec![KeyCode::C],
+        }
+    }
+}
+
+/// The keys bound to each action, loaded from the settings file of the player.
+/// An action missing from the file keeps its default keys.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct InputBindings {
+    bindings: BTreeMap<InputAction, Vec<KeyCode>>,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        InputBindings {
+            bindings: InputAction::ALL
+                .iter()
+                .map(|action| (*action, action.get_default_keys()))
+                .collect(),
+        }
+    }
+}
+
+impl InputBindings {
+    /// Read the bindings from the content of a RON settings file
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///     # use bevy::prelude::*;
+    ///     # use street_of_zombies::game_system::input_bindings::{InputAction, InputBindings};
+    ///     let input_bindings = InputBindings::from_ron("(bindings: { Fire: [ControlLeft] })").unwrap();
+    ///     assert_eq!(input_bindings.get_keys(InputAction::Fire), &[KeyCode::ControlLeft]);
+    /// ```
+    pub fn from_ron(content: &str) -> Result<Self, String> {
+        let mut input_bindings: InputBindings =
+            ron::from_str(content).map_err(|error| format!("Invalid settings: {}", error))?;
+
+        for action in InputAction::ALL {
+            input_bindings
+                .bindings
+                .entry(action)
+                .or_insert_with(|| action.get_default_keys());
+        }
+
+        Ok(input_bindings)
+    }
+
+    /// Load the settings file, or the default bindings when the file does not exist
+    pub fn load_or_default(path: &str) -> Result<Self, String> {
+        if fs::metadata(path).is_err() {
+            return Ok(InputBindings::default());
+        }
+
+        let content =
+            fs::read_to_string(path).map_err(|error| format!("Cannot read {}: {}", path, error))?;
+        InputBindings::from_ron(&content).map_err(|error| format!("{}: {}", path, error))
+    }
+
+    /// Save the bindings in the settings file
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let content = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|error| format!("Cannot write the settings: {}", error))?;
+        fs::write(path, content).map_err(|error| format!("Cannot write {}: {}", path, error))
+    }
+
+    /// Get the keys bound to an action
+    pub fn get_keys(&self, action: InputAction) -> &[KeyCode] {
+        self.bindings.get(&action).map_or(&[], |keys| keys.as_slice())
+    }
+
+    /// Get the names of the keys bound to an action, as printed on the screens of the game
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///     # use street_of_zombies::game_system::input_bindings::{InputAction, InputBindings};
+    ///     assert_eq!(InputBindings::default().get_keys_name(InputAction::Pause), "P / Escape");
+    /// ```
+    pub fn get_keys_name(&self, action: InputAction) -> String {
+        self.get_keys(action)
+            .iter()
+            .map(|key| format!("{:?}", key))
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+
+    /// Bind a single key to an action, in place of its previous keys
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The action to rebind
+    /// * `key` - The new key of the action
+    ///
+    pub fn set_key(&mut self, action: InputAction, key: KeyCode) {
+        self.bindings.insert(action, vec![key]);
+    }
+
+    /// Bind the default keys to an action
+    pub fn reset_keys(&mut self, action: InputAction) {
+        self.bindings.insert(action, action.get_default_keys());
+    }
+
+    /// Return true while a key of the action is held
+    pub fn pressed(&self, keyboard_input: &Input<KeyCode>, action: InputAction) -> bool {
+        keyboard_input.any_pressed(self.get_keys(action).iter().copied())
+    }
+
+    /// Return true when a key of the action was just pressed
+    pub fn just_pressed(&self, keyboard_input: &Input<KeyCode>, action: InputAction) -> bool {
+        keyboard_input.any_just_pressed(self.get_keys(action).iter().copied())
+    }
+}
+
+/// The settings file where the rebound keys are saved. The bindings are not saved without this resource
+#[derive(Resource)]
+pub struct SettingsFile {
+    pub path: String,
+}
+
+/// The state of the rebind screen
+#[derive(Resource, Default)]
+pub struct RebindMenu {
+    /// Index of the selected action in `InputAction::ALL`
+    selected: usize,
+    /// True while the rebind screen waits for the new key of the selected action
+    waiting_for_key: bool,
+}
+
+impl RebindMenu {
+    /// Get the selected action
+    pub fn get_selected_action(&self) -> InputAction {
+        InputAction::ALL[self.selected]
+    }
+
+    /// Return true while the rebind screen waits for the new key of the selected action
+    pub fn is_waiting_for_key(&self) -> bool {
+        self.waiting_for_key
+    }
+}
+
+/// The rebind screen: Up / Down to select an action, Enter then a key to rebind it, Backspace to restore its
+/// default keys, Escape to save and go back to the main menu. These keys cannot be rebound, the player never gets stuck.
+/// Managed by as a "Bevy System"
+pub fn rebind_menu_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut rebind_menu: ResMut<RebindMenu>,
+    mut input_bindings: ResMut<InputBindings>,
+    settings_file: Option<Res<SettingsFile>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if rebind_menu.waiting_for_key {
+        if keyboard_input.just_pressed(KeyCode::Escape) {
+            rebind_menu.waiting_for_key = false;
+        } else if let Some(key) = keyboard_input.get_just_pressed().next() {
+            let action = rebind_menu.get_selected_action();
+            input_bindings.set_key(action, *key);
+            rebind_menu.waiting_for_key = false;
+        }
+        return;
+    }
+
+    let number_of_actions = InputAction::ALL.len();
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        rebind_menu.selected = (rebind_menu.selected + number_of_actions - 1) % number_of_actions;
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        rebind_menu.selected = (rebind_menu.selected + 1) % number_of_actions;
+    } else if keyboard_input.just_pressed(KeyCode::Return) {
+        rebind_menu.waiting_for_key = true;
+    } else if keyboard_input.just_pressed(KeyCode::Back) {
+        let action = rebind_menu.get_selected_action();
+        input_bindings.reset_keys(action);
+    } else if keyboard_input.just_pressed(KeyCode::Escape) {
+        if let Some(settings_file) = settings_file {
+            if let Err(error) = input_bindings.save(&settings_file.path) {
+                error!("{}", error);
+            }
+        }
+        next_state.set(GameState::MainMenu);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_actions_keep_their_default_keys() {
+        let input_bindings = InputBindings::from_ron("(bindings: { Fire: [ControlLeft] })").unwrap();
+
+        assert_eq!(input_bindings.get_keys(InputAction::Fire), &[KeyCode::ControlLeft]);
+        assert_eq!(input_bindings.get_keys(InputAction::MoveUp), &[KeyCode::Up, KeyCode::W]);
+        assert_eq!(input_bindings.get_keys(InputAction::WeaponSlot1), &[KeyCode::Key1]);
+        assert_eq!(input_bindings.get_keys(InputAction::WeaponSlot9), &[KeyCode::Key9]);
+        assert_eq!(input_bindings.get_keys(InputAction::OpenControls), &[KeyCode::C]);
+    }
+
+    #[test]
+    fn saved_bindings_are_loaded_back() {
+        let mut input_bindings = InputBindings::default();
+        input_bindings.set_key(InputAction::Reload, KeyCode::F);
+        input_bindings.set_key(InputAction::WeaponSlot2, KeyCode::Numpad2);
+        input_bindings.set_key(InputAction::OpenControls, KeyCode::F1);
+
+        let content = ron::ser::to_string_pretty(&input_bindings, ron::ser::PrettyConfig::default()).unwrap();
+        assert_eq!(InputBindings::from_ron(&content), Ok(input_bindings));
+    }
+
+    #[test]
+    fn rebound_keys_are_named() {
+        let mut input_bindings = InputBindings::default();
+        assert_eq!(input_bindings.get_keys_name(InputAction::MoveUp), "Up / W");
+
+        input_bindings.set_key(InputAction::Restart, KeyCode::F5);
+        assert_eq!(input_bindings.get_keys_name(InputAction::Restart), "F5");
+    }
+
+    #[test]
+    fn wasd_and_arrows_move_the_player() {
+        let input_bindings = InputBindings::default();
+        let mut keyboard_input = Input::<KeyCode>::default();
+
+        keyboard_input.press(KeyCode::A);
+        assert!(input_bindings.pressed(&keyboard_input, InputAction::MoveLeft));
+        keyboard_input.release(KeyCode::A);
+        keyboard_input.press(KeyCode::Left);
+        assert!(input_bindings.pressed(&keyboard_input, InputAction::MoveLeft));
+        assert!(!input_bindings.pressed(&keyboard_input, InputAction::MoveRight));
+    }
+}
@@ -1,70 +1,188 @@
-use rand::Rng;
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
-/// Calculate the distance between two objects in a "cartesian referencial".
-/// Calculate from the "first point" to the "second point"
+/// The random number generator of the game. Every random decision of the gameplay uses it,
+/// so the same seed (and the same inputs) reproduces the same game.
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    generator: StdRng,
+}
+
+impl GameRng {
+    /// Returns a new GameRng initialized with the given seed
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed of the random number generator
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///     # use street_of_zombies::game_system::math_and_generator::GameRng;
+    ///     let game_rng = GameRng::new(42);
+    ///     assert_eq!(game_rng.get_seed(), 42);
+    /// ```
+    pub fn new(seed: u64) -> Self {
+        GameRng {
+            seed,
+            generator: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Get the seed used to initialize the generator
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Restart the generator from its seed: each new game gets the same random numbers as the first one
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///     # use rand::Rng;
+    ///     # use street_of_zombies::game_system::math_and_generator::GameRng;
+    ///     let mut game_rng = GameRng::new(42);
+    ///     let first_number: u32 = game_rng.generator().gen();
+    ///     game_rng.reseed();
+    ///     assert_eq!(game_rng.generator().gen::<u32>(), first_number);
+    /// ```
+    pub fn reseed(&mut self) {
+        self.generator = StdRng::seed_from_u64(self.seed);
+    }
+
+    /// Get the random number generator
+    pub fn generator(&mut self) -> &mut StdRng {
+        &mut self.generator
+    }
+}
+
+impl Default for GameRng {
+    /// A GameRng with a random seed
+    fn default() -> Self {
+        GameRng::new(rand::thread_rng().gen())
+    }
+}
+
+/// Rotate a direction factor by an angle, its length is kept
 ///
 /// # Arguments
 ///
-/// * `from_coord` - The coordinate of the first point (x, y)
-/// * `to_coord` - The coordinate of the second point (x, y)
+/// * `direction` - The direction factor
+/// * `angle` - The angle of the rotation in radians, counterclockwise
 ///
 /// # Examples
 ///
 /// ```
-///     let from_point: (f32, f32) = (50., 50.);
-///     let to_point: (f32, f32) = (10., 10.);
-///     let calculated_distance = calculate_cartesian_distance(from_point, to_point);
-///     assert_eq!(calculated_distance, 80.);
+///     # use bevy::prelude::*;
+///     # use street_of_zombies::game_system::math_and_generator::*;
+///     let rotated_direction = rotate_direction(Vec2::new(1., 0.), std::f32::consts::FRAC_PI_2);
 /// ```
-pub fn calculate_cartesian_distance(from_coord: (f32, f32), to_coord: (f32, f32)) -> f32 {
-    let (from_x, from_y) = from_coord;
-    let (to_x, to_y) = to_coord;
-
-    let position_diff_x = (from_x - to_x) as i32;
-    let position_diff_y = (from_y - to_y) as i32;
-    let distance_walked_squared = (position_diff_x.pow(2) + position_diff_y.pow(2)) as f32;
-
-    distance_walked_squared.sqrt()
+pub fn rotate_direction(direction: Vec2, angle: f32) -> Vec2 {
+    Vec2::from_angle(angle).rotate(direction)
 }
 
-/// Generate a random direction factor in a tuple (x, y) (without x.abs() + y.abs() = 1)
+/// Generate a random direction factor (without x.abs() + y.abs() = 1)
+///
+/// # Arguments
+///
+/// * `rng` - The random number generator
 ///
 /// # Examples
 ///
 /// ```
-///     let my_direction_factor = generate_random_direction_factor();
+///     # use bevy::prelude::*;
+///     # use street_of_zombies::game_system::math_and_generator::*;
+///     # let mut game_rng = GameRng::new(42);
+///     let my_direction_factor = generate_random_direction_factor(game_rng.generator());
 /// ```
-pub fn generate_random_direction_factor() -> (f32, f32) {
-    let mut rng = rand::thread_rng();
-
+pub fn generate_random_direction_factor(rng: &mut impl Rng) -> Vec2 {
     let random_direction_factor_x: f32 = rng.gen_range(-1.0..1.0);
     let random_direction_factor_y: f32 = rng.gen_range(-1.0..1.0);
 
-    (random_direction_factor_x, random_direction_factor_y)
+    Vec2::new(random_direction_factor_x, random_direction_factor_y)
 }
 
 /// Generate a random position limited to the entry value (in a square)
 ///
 /// # Arguments
 ///
+/// * `rng` - The random number generator
 /// * `absolute_max_x` - The maximal coordinate in X axe from -absolute_max_x to absolute_max_x
 /// * `absolute_max_y` - The maximal coordinate in Y axe from -absolute_max_y to absolute_max_y
 ///
 /// # Examples
 ///
 /// ```
+///     # use bevy::prelude::*;
+///     # use street_of_zombies::game_system::math_and_generator::*;
+///     # let mut game_rng = GameRng::new(42);
 ///     // Generate a random number in the range (-500..500, -200..200)
-///     let my_direction_factor = generate_random_position(500., 200.);
+///     let my_direction_factor = generate_random_position(game_rng.generator(), 500., 200.);
 /// ```
-pub fn generate_random_position(absolute_max_x: f32, absolute_max_y: f32) -> (f32, f32) {
-    let mut rng = rand::thread_rng();
-
-    let generated_position: (f32, f32) = (
+pub fn generate_random_position(
+    rng: &mut impl Rng,
+    absolute_max_x: f32,
+    absolute_max_y: f32,
+) -> Vec2 {
+    Vec2::new(
         rng.gen_range(-absolute_max_x..absolute_max_x),
         rng.gen_range(-absolute_max_y..absolute_max_y),
-    );
+    )
+}
 
-    generated_position
+/// Get the direction of fire to hit a moving target, leading the target by the travel time of the projectile.
+/// The target is aimed directly when the projectile is too slow to catch it
+///
+/// # Arguments
+///
+/// * `shooter_position` - The position where the projectile is launched
+/// * `target_position` - The current position of the target
+/// * `target_velocity` - The velocity of the target, in pixels per second
+/// * `projectile_speed` - The speed of the projectile, in pixels per second
+///
+/// # Examples
+///
+/// ```
+///     # use bevy::prelude::*;
+///     # use street_of_zombies::game_system::math_and_generator::*;
+///     // A target running to the right is aimed ahead of its position
+///     let direction = get_intercept_direction(Vec2::ZERO, Vec2::new(0., 300.), Vec2::new(100., 0.), 300.);
+///     assert!(direction.x > 0.);
+/// ```
+pub fn get_intercept_direction(
+    shooter_position: Vec2,
+    target_position: Vec2,
+    target_velocity: Vec2,
+    projectile_speed: f32,
+) -> Vec2 {
+    let to_target = target_position - shooter_position;
+    let direct_aim = to_target.try_normalize().unwrap_or(Vec2::Y);
+
+    // |to_target + target_velocity * t| = projectile_speed * t
+    let a = target_velocity.length_squared() - projectile_speed * projectile_speed;
+    let b = 2. * to_target.dot(target_velocity);
+    let c = to_target.length_squared();
+
+    let time_of_impact = if a.abs() < f32::EPSILON {
+        (b < 0.).then(|| -c / b)
+    } else {
+        let discriminant = b * b - 4. * a * c;
+        if discriminant < 0. {
+            None
+        } else {
+            let square_root = discriminant.sqrt();
+            [(-b - square_root) / (2. * a), (-b + square_root) / (2. * a)]
+                .into_iter()
+                .filter(|time| *time > 0.)
+                .reduce(f32::min)
+        }
+    };
+
+    time_of_impact
+        .and_then(|time| (to_target + target_velocity * time).try_normalize())
+        .unwrap_or(direct_aim)
 }
 
 #[cfg(test)]
@@ -72,27 +190,61 @@ mod tests {
     use super::*;
 
     #[test]
-    fn calculate_cartesian_distance_positive() {
-        let from_point: (f32, f32) = (50., 50.);
-        let to_point: (f32, f32) = (10., 10.);
-        let calculated_distance = calculate_cartesian_distance(from_point, to_point);
-        assert_eq!(calculated_distance, 56.568542);
+    fn rotate_direction_quarter_turn() {
+        let rotated_direction = rotate_direction(Vec2::new(1., 0.), std::f32::consts::FRAC_PI_2);
+
+        assert!(rotated_direction.abs_diff_eq(Vec2::new(0., 1.), 0.0001));
     }
 
     #[test]
-    fn calculate_cartesian_distance_0() {
-        let from_point: (f32, f32) = (0., 0.);
-        let to_point: (f32, f32) = (0., 0.);
-        let calculated_distance = calculate_cartesian_distance(from_point, to_point);
-        assert_eq!(calculated_distance, 0.);
+    fn intercept_direction_hits_the_moving_target() {
+        let shooter_position = Vec2::new(10., -50.);
+        let target_position = Vec2::new(200., 150.);
+        let target_velocity = Vec2::new(-120., 40.);
+        let projectile_speed = 300.;
+
+        let direction =
+            get_intercept_direction(shooter_position, target_position, target_velocity, projectile_speed);
+
+        // The projectile and the target meet at the same moment
+        let to_target = target_position - shooter_position;
+        let time = to_target.length() / (direction * projectile_speed - target_velocity).length();
+        let projectile_position = shooter_position + direction * projectile_speed * time;
+        assert!(projectile_position.abs_diff_eq(target_position + target_velocity * time, 0.1));
     }
 
     #[test]
-    fn calculate_cartesian_distance_negative() {
-        let from_point: (f32, f32) = (-10., -10.);
-        let to_point: (f32, f32) = (-50., -50.);
-        let calculated_distance = calculate_cartesian_distance(from_point, to_point);
-        assert_eq!(calculated_distance, 56.568542);
+    fn too_fast_target_is_aimed_directly() {
+        let direction = get_intercept_direction(Vec2::ZERO, Vec2::new(0., 100.), Vec2::new(0., 500.), 300.);
+
+        assert!(direction.abs_diff_eq(Vec2::new(0., 1.), 0.0001));
     }
 
+    #[test]
+    fn same_seed_same_positions() {
+        let mut first_rng = GameRng::new(1234);
+        let mut second_rng = GameRng::new(1234);
+
+        for _ in 0..10 {
+            assert_eq!(
+                generate_random_position(first_rng.generator(), 500., 200.),
+                generate_random_position(second_rng.generator(), 500., 200.)
+            );
+            assert_eq!(
+                generate_random_direction_factor(first_rng.generator()),
+                generate_random_direction_factor(second_rng.generator())
+            );
+        }
+    }
+
+    #[test]
+    fn random_position_inside_limits() {
+        let mut game_rng = GameRng::new(42);
+
+        for _ in 0..100 {
+            let position = generate_random_position(game_rng.generator(), 500., 200.);
+            assert!(position.x.abs() <= 500.);
+            assert!(position.y.abs() <= 200.);
+        }
+    }
 }
@@ -1,278 +1,495 @@
+pub mod bonus_pickup_gameplay;
+pub mod boss_gameplay;
 pub mod ennemy_spawn_ai_gameplay;
+pub mod game_config;
+pub mod game_state;
+pub mod gameplay_events;
+pub mod health_gameplay;
+pub mod input_bindings;
 pub mod math_and_generator;
+pub mod player_input;
 pub mod projectile_and_kill_gameplay;
-
-mod scoreboard;
+pub mod replay;
+pub mod scoreboard;
+pub mod spatial_grid;
 
 use crate::game_entity::*;
 use crate::game_entity::ennemies::Ennemy;
+use crate::game_entity::health::Health;
+use crate::game_entity::pickups::Pickup;
 use crate::game_entity::player::Player;
 use crate::game_entity::projectiles::Projectile;
+use crate::game_system::game_config::{GameAreaConfig, GameConfig, GameConfigWatcher};
+use crate::game_system::game_state::GameState;
+use crate::game_system::gameplay_events::{DifficultyIncreased, EnnemyHit, EnnemyKilled, PlayerDied, PlayerHit, ShotFired};
+use crate::game_system::input_bindings::{InputBindings, RebindMenu};
+use crate::game_system::player_input::PlayerInput;
+use crate::game_system::replay::{ReplayPlayer, ReplayRecorder};
 use crate::game_system::scoreboard::ScoreAndInfo;
-use crate::sprite_manager_system::*;
 
 use bevy::prelude::*;
 
-// Game area limit
-pub static GAME_AREA_LIMIT_X: f32 = 500.0;
-pub static GAME_AREA_LIMIT_Y: f32 = 300.0;
-
-static MAXIMUM_ENNEMY_DISTANCE: f32 = 300.;
-static INITIAL_ENNEMY_SPEED: f32 = 200.0;
+/// Number of gameplay updates per second (fixed timestep, independent of the frame rate)
+pub static GAMEPLAY_TICKS_PER_SECOND: f64 = 60.0;
 
-// Main character initialization
-static INITIAL_PLAYER_POSITION_X: f32 = 0.0;
-static INITIAL_PLAYER_POSITION_Y: f32 = -215.0;
-static INITIAL_PLAYER_SPEED: f32 = 350.0;
-static INITIAL_PLAYER_DIRECTION: (f32, f32) = (0.0, 1.0);
+/// The gameplay rules of the game: player, ennemies, projectiles and score.
+///
+/// This plugin does not load any asset and does not draw anything, it can run under `MinimalPlugins`
+/// (with the `InputPlugin`) for headless simulations.
+/// Insert a `GameRng` resource before adding the plugin to choose the seed of the game.
+/// Insert a `GameConfig` to change the tuning of the game, and a `GameConfigWatcher` to reload it when its file changes
+/// (the configuration is never reloaded while a replay is recorded or played).
+/// Insert `InputBindings` to change the keys of the player, and a `SettingsFile` to save the keys rebound in the game.
+/// Insert a `ReplayRecorder` to record the inputs of the player, or a `ReplayPlayer` to play a replay instead of the keyboard.
+///
+/// Movements, fire and collisions run in the `FixedUpdate` schedule (`GAMEPLAY_TICKS_PER_SECOND`),
+/// the inputs are captured in `Update`.
+/// The game starts in the `GameState::MainMenu` state, the gameplay only runs in the `GameState::Playing` state.
+pub struct StreetOfZombiesGameplay;
 
-pub struct StreetOfZombiesEngine;
+/// The gameplay systems of a single fixed tick
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GameplaySimulationSet;
 
-impl Plugin for StreetOfZombiesEngine {
+impl Plugin for StreetOfZombiesGameplay {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, (setup, set_window_parameters))
+        app.init_resource::<GameConfig>()
+            .add_state::<GameState>()
+            .init_resource::<ScoreAndInfo>()
+            .insert_resource(Time::<Fixed>::from_hz(GAMEPLAY_TICKS_PER_SECOND))
+            .init_resource::<math_and_generator::GameRng>()
+            .init_resource::<PlayerInput>()
+            .init_resource::<player_input::AimMode>()
+            .init_resource::<InputBindings>()
+            .init_resource::<RebindMenu>()
+            .init_resource::<game_state::GamepadDisconnectionPause>()
+            .init_resource::<spatial_grid::SpatialGrid>()
+            .add_event::<EnnemyHit>()
+            .add_event::<EnnemyKilled>()
+            .add_event::<PlayerHit>()
+            .add_event::<PlayerDied>()
+            .add_event::<DifficultyIncreased>()
+            .add_event::<ShotFired>()
+            .add_systems(OnTransition { from: GameState::MainMenu, to: GameState::Playing }, start_new_game)
+            .add_systems(OnExit(GameState::GameOver), start_new_game)
+            .add_systems(OnEnter(GameState::Paused), game_state::pause_game)
+            .add_systems(OnExit(GameState::Paused), game_state::resume_game)
             .add_systems(Update, (
-                keyboard_capture,
+                player_input::keyboard_capture.run_if(not(resource_exists::<ReplayPlayer>())),
+                player_input::gamepad_capture.run_if(not(resource_exists::<ReplayPlayer>())),
+                player_input::aim_capture.run_if(not(resource_exists::<ReplayPlayer>())),
+                game_state::gamepad_connection_system.run_if(not(resource_exists::<ReplayPlayer>())),
+                game_state::replay_state_system.run_if(resource_exists::<ReplayPlayer>()),
+                game_state::state_input_system,
+            ).chain())
+            .add_systems(Update, input_bindings::rebind_menu_system.run_if(in_state(GameState::Controls)))
+            .add_systems(
+                Update,
+                game_config::hot_reload_game_config_system.run_if(
+                    resource_exists::<GameConfigWatcher>()
+                        .and_then(not(resource_exists::<ReplayRecorder>()))
+                        .and_then(not(resource_exists::<ReplayPlayer>())),
+                ),
+            )
+            .add_systems(Last, replay::save_replay_on_exit_system.run_if(resource_exists::<ReplayRecorder>()))
+            .configure_sets(FixedUpdate, GameplaySimulationSet.run_if(game_state::is_gameplay_running))
+            .add_systems(FixedUpdate, (
+                replay::replay_input_system.run_if(resource_exists::<ReplayPlayer>()),
+                replay::record_input_system.run_if(resource_exists::<ReplayRecorder>()),
+                player_input::player_control_system,
                 projectile_and_kill_gameplay::projectile_movement_system,
-                projectile_and_kill_gameplay::projectile_collision_and_score_system,
+                spatial_grid::rebuild_spatial_grid_system,
+                projectile_and_kill_gameplay::projectile_collision_system,
+                projectile_and_kill_gameplay::ennemy_contact_damage_system,
+                health_gameplay::death_system,
+                projectile_and_kill_gameplay::ennemy_death_explosion_system,
+                bonus_pickup_gameplay::drop_pickup_system,
+                bonus_pickup_gameplay::pickup_collection_system,
+                bonus_pickup_gameplay::pickup_timeout_system,
+                bonus_pickup_gameplay::player_timed_effects_system,
+                health_gameplay::health_regeneration_system,
+                scoreboard::difficulty_timer_system,
+                (boss_gameplay::boss_encounter_system, boss_gameplay::boss_attack_system).chain(),
                 ennemy_spawn_ai_gameplay::ennemy_ai_system,
-                restart_on_r_system,
-            ))
-            .add_systems(Update, animate_sprite_system.after(keyboard_capture));
+                scoreboard::score_system,
+                player_input::clear_requests_system,
+                game_state::game_over_system,
+            ).chain().in_set(GameplaySimulationSet));
     }
 }
 
-/// Initial setup
-pub fn setup(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    texture_atlases: ResMut<Assets<TextureAtlas>>
-) {
-    // cameras
-    commands.spawn(Camera2dBundle::default());
-
-    // Background image
-    let background_image: Handle<Image> =
-        asset_server.load("images/background_street_of_zombies.png");
-    commands.spawn(SpriteBundle {
-        texture: background_image,
-        ..Default::default()
-    });
-
-    spawn_player_and_score(commands, asset_server, texture_atlases);
-}
+/// Spawn the main character, controllable by the player
+fn spawn_player(mut commands: Commands, game_config: &GameConfig) {
+    let initial_position = game_config.player.initial_position;
 
-fn spawn_player_and_score(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut texture_atlases: ResMut<Assets<TextureAtlas>>) 
-{
-    // Main character
     commands
-        .spawn(SpriteSheetBundle {
-            texture_atlas: generate_texture(
-                &asset_server,
-                &mut texture_atlases,
-                TextureToGenerate::PLAYER,
-            ),
-            transform: Transform::from_xyz(
-                INITIAL_PLAYER_POSITION_X,
-                INITIAL_PLAYER_POSITION_Y,
-                0.0,
-            ),
-            sprite: TextureAtlasSprite::new(1),
-            ..Default::default()
-        })
-        .insert(player::Player::new(
-            INITIAL_PLAYER_SPEED,
-            INITIAL_PLAYER_DIRECTION,
-            (INITIAL_PLAYER_POSITION_X, INITIAL_PLAYER_POSITION_Y),
-        ))
-        .insert(AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)));
-
-    // Scoreboard
-    commands
-        .spawn(TextBundle::from_sections([
-            TextSection::new(
-                "Score",
-                TextStyle {
-                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                    font_size: 40.0,
-                    color: Color::rgb(0.5, 0.5, 1.0),
-                },
-            ),
-            TextSection::new(
-                "health",
-                TextStyle {
-                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                    font_size: 40.0,
-                    color: Color::rgb(0.5, 1.0, 0.5),
-                },
-            ),
-            TextSection::new(
-                "Difficulty",
-                TextStyle {
-                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                    font_size: 40.0,
-                    color: Color::rgb(1.0, 1.0, 1.0),
-                },
-            ),
-        ])
-        .with_style(Style {
-            position_type: PositionType::Absolute,
-            top: Val::Px(0.0),
-            left: Val::Px(0.0),
-            ..Default::default()
-        }))
-        .insert(scoreboard::ScoreAndInfo::new());
-
-    // Hidden ennemy (quick texture load)
-    // This is a "pre-load" of the zombie texture.
-    // Avoid to show a "Zombies" with a player Sprite for few milliseconds.
-    commands.spawn(SpriteSheetBundle {
-        texture_atlas: generate_texture(
-            &asset_server,
-            &mut texture_atlases,
-            TextureToGenerate::ZOMBIE,
-        ),
-        transform: Transform::from_xyz(GAME_AREA_LIMIT_X + 50., GAME_AREA_LIMIT_Y + 50., 0.0),
-        sprite: TextureAtlasSprite::new(1),
-        visibility: Visibility::Hidden,
-        ..Default::default()
-    });
-}
-
-/// Capture the keyboard entry to move or fire with the player entity. Managed by as a "Bevy System"
-pub fn keyboard_capture(
-    mut commands: Commands,
-    time: Res<Time>,
-    keyboard_input: Res<Input<KeyCode>>,
-    mut query: Query<(&mut player::Player, &mut Transform)>,
-) {
-    if let Ok((mut player, mut transform)) = query.get_single_mut() {
-        let mut direction: (f32, f32) = (0.0, 0.0);
-        let mut number_of_valid_pressure: u8 = 0;
-
-        // Fire capture
-        if keyboard_input.pressed(KeyCode::Space) {
-            player.fire(&mut commands, &time);
-        } else {
-            player.reload_weapon();
-        }
-
-        // Movement
-        if keyboard_input.pressed(KeyCode::Left) {
-            direction.0 = -1.0;
-            number_of_valid_pressure += 1;
-        }
-        if keyboard_input.pressed(KeyCode::Right) {
-            direction.0 = 1.0;
-            number_of_valid_pressure += 1;
-        }
-        if keyboard_input.pressed(KeyCode::Up) {
-            direction.1 = 1.0;
-            number_of_valid_pressure += 1;
-        }
-        if keyboard_input.pressed(KeyCode::Down) {
-            direction.1 = -1.0;
-            number_of_valid_pressure += 1;
-        }
-
-        match number_of_valid_pressure {
-            0 => return,
-            1 => (),
-            _ => {
-                direction.0 = (direction.0.abs() - 0.33) * direction.0;
-                direction.1 = (direction.1.abs() - 0.33) * direction.1;
-            }
-        }
-
-        player.get_moveable_interface_mut().move_sprite(
-            &time,
-            &direction,
-            &mut transform.translation,
+        .spawn(TransformBundle::from_transform(Transform::from_translation(initial_position.extend(0.0))))
+        .insert(player::Player::new(&game_config.player, &game_config.weapons))
+        .insert(
+            Health::new(game_config.player.health)
+                .with_regen(game_config.player.health_regen)
+                .with_armor(game_config.player.armor),
         );
-    }
 }
 
 /// Check if the next movement will be out of the game area.
 ///
 /// # Arguments
 ///
-/// * `position` - The current coordinate of the entity in a cartesian graph (x, y)
-/// * `direction_factor` - The direction factor of the entity in a cartesian graph (x, y)
+/// * `position` - The current coordinate of the entity in a cartesian graph
+/// * `direction_factor` - The direction factor of the entity in a cartesian graph
+/// * `game_area` - The limits of the game area
 ///
 /// ```
 pub fn is_next_movement_out_of_game_area(
-    position: (f32, f32),
-    direction_factor: (f32, f32),
+    position: Vec2,
+    direction_factor: Vec2,
+    game_area: &GameAreaConfig,
 ) -> bool {
-    let next_movement_coord: (f32, f32) = (
-        (position.0 + direction_factor.0).abs(),
-        (position.1 + direction_factor.1).abs(),
-    );
+    let next_movement_coord = (position + direction_factor).abs();
 
-    next_movement_coord.0 > GAME_AREA_LIMIT_X || next_movement_coord.1 > GAME_AREA_LIMIT_Y
+    next_movement_coord.x > game_area.limit_x || next_movement_coord.y > game_area.limit_y
 }
 
-/// This "Startup-Item" modify the Window parameter (title and no-resize)
-fn set_window_parameters(mut windows: Query<&mut Window>) {
-    if let Ok(mut window) = windows.get_single_mut() {
-        window.title = "Street of Zombies".to_string();
-        window.resizable = false;
-    }
-}
+/// Every entity of a game, removed when a new game starts
+type GameEntityFilter = Or<(With<Player>, With<Ennemy>, With<Projectile>, With<Pickup>)>;
 
-/// System to restart the game when R is pressed after game over
-fn restart_on_r_system(
+/// Clean the game area of the previous game, reset the score and the random number generator, then spawn the main character.
+/// Every game of a seed is played with the same random numbers
+fn start_new_game(
     mut commands: Commands,
-    keyboard_input: Res<Input<KeyCode>>,
-    player_query: Query<Entity, With<Player>>,
-    ennemy_query: Query<Entity, With<Ennemy>>,
-    projectile_query: Query<Entity, With<Projectile>>,
-    scoreboard_entity_query: Query<Entity, With<ScoreAndInfo>>,
-    scoreboard_state_query: Query<&ScoreAndInfo>,
-    asset_server: Res<AssetServer>,
-    texture_atlases: ResMut<Assets<TextureAtlas>>,
+    mut player_input: ResMut<PlayerInput>,
+    game_entity_query: Query<Entity, GameEntityFilter>,
+    mut score: ResMut<ScoreAndInfo>,
+    mut game_rng: ResMut<math_and_generator::GameRng>,
+    game_config: Res<GameConfig>,
 ) {
-    // Only allow restart if game is over
-    let is_gameover = scoreboard_state_query.iter().any(|scoreboard| scoreboard.is_gameover());
+    for entity in game_entity_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    // Reset the score, the random number generator and the main character
+    player_input.restart = false;
+    *score = ScoreAndInfo::new();
+    game_rng.reseed();
+    spawn_player(commands, &game_config);
+}
 
-    if is_gameover && keyboard_input.just_pressed(KeyCode::R) {
-        // Despawn all relevant entities
-        for entity in player_query.iter() {
-            commands.entity(entity).despawn_recursive();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_system::input_bindings::InputAction;
+    use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent, GamepadInfo};
+    use bevy::input::keyboard::KeyboardInput;
+    use bevy::input::{ButtonState, InputPlugin};
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    #[test]
+    fn next_movement_out_of_game_area() {
+        let game_area = GameAreaConfig::default();
+        assert!(is_next_movement_out_of_game_area(
+            Vec2::new(game_area.limit_x, game_area.limit_y),
+            Vec2::new(1., 1.),
+            &game_area
+        ));
+    }
+
+    #[test]
+    fn next_movement_inside_of_game_area() {
+        assert!(!is_next_movement_out_of_game_area(
+            Vec2::ZERO,
+            Vec2::ZERO,
+            &GameAreaConfig::default()
+        ));
+    }
+
+    /// Leave the main menu at the next update
+    fn start_game(app: &mut App) {
+        app.world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Playing);
+    }
+
+    #[test]
+    fn gameplay_runs_without_presentation() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, InputPlugin, StreetOfZombiesGameplay))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+                1.0 / GAMEPLAY_TICKS_PER_SECOND,
+            )));
+        start_game(&mut app);
+
+        for _ in 0..120 {
+            app.update();
         }
-        for entity in ennemy_query.iter() {
-            commands.entity(entity).despawn_recursive();
+
+        let mut player_query = app.world.query::<&Player>();
+        assert_eq!(player_query.iter(&app.world).count(), 1);
+        assert!(!app.world.resource::<ScoreAndInfo>().is_gameover());
+    }
+
+    /// Simulate twenty seconds of gameplay with the given frame duration, return the position of each ennemy
+    fn simulate_twenty_seconds(frame_duration: Duration) -> Vec<Vec2> {
+        let mut app = App::new();
+        app.insert_resource(math_and_generator::GameRng::new(42))
+            .add_plugins((MinimalPlugins, InputPlugin, StreetOfZombiesGameplay))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(frame_duration));
+        start_game(&mut app);
+
+        // The first frame of the app does not advance the time, count the gameplay ticks instead of the frames
+        let twenty_seconds_of_ticks = app.world.resource::<Time<Fixed>>().timestep() * 1200;
+        while app.world.resource::<Time<Fixed>>().elapsed() < twenty_seconds_of_ticks {
+            app.update();
         }
-        for entity in projectile_query.iter() {
-            commands.entity(entity).despawn_recursive();
+
+        let mut ennemy_query = app.world.query_filtered::<&Transform, With<Ennemy>>();
+        ennemy_query.iter(&app.world).map(get_position).collect()
+    }
+
+    /// Play the given number of gameplay ticks, return the position of each ennemy
+    fn play_ticks(app: &mut App, ticks: u32) -> Vec<Vec2> {
+        let end_of_play = app.world.resource::<Time<Fixed>>().elapsed()
+            + app.world.resource::<Time<Fixed>>().timestep() * ticks;
+        while app.world.resource::<Time<Fixed>>().elapsed() < end_of_play {
+            app.update();
         }
-        for entity in scoreboard_entity_query.iter() {
-            commands.entity(entity).despawn_recursive();
+
+        let mut ennemy_query = app.world.query_filtered::<&Transform, With<Ennemy>>();
+        ennemy_query.iter(&app.world).map(get_position).collect()
+    }
+
+    #[test]
+    fn restarted_games_follow_the_seed() {
+        let mut app = App::new();
+        app.insert_resource(math_and_generator::GameRng::new(5))
+            .add_plugins((MinimalPlugins, InputPlugin, StreetOfZombiesGameplay))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+                1.0 / GAMEPLAY_TICKS_PER_SECOND,
+            )));
+        start_game(&mut app);
+        let first_game = play_ticks(&mut app, 600);
+
+        let mut restarted_games = Vec::new();
+        for _ in 0..2 {
+            app.world
+                .resource_mut::<NextState<GameState>>()
+                .set(GameState::GameOver);
+            app.update();
+            start_game(&mut app);
+            restarted_games.push(play_ticks(&mut app, 600));
         }
-        // Optionally, despawn other entities (background, etc.) if needed
 
-        // Re-run setup to reset the game
-        spawn_player_and_score(commands, asset_server, texture_atlases);
+        assert!(!first_game.is_empty());
+        assert_eq!(restarted_games[0], first_game);
+        assert_eq!(restarted_games[1], first_game);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn main_menu_waits_for_the_player() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, InputPlugin, StreetOfZombiesGameplay));
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let mut player_query = app.world.query::<&Player>();
+        assert_eq!(player_query.iter(&app.world).count(), 0);
+
+        // "just pressed" is cleared at the beginning of each update, send the key event instead
+        app.world.send_event(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(KeyCode::Return),
+            state: ButtonState::Pressed,
+            window: Entity::PLACEHOLDER,
+        });
+        app.update();
+        app.update();
+
+        assert_eq!(player_query.iter(&app.world).count(), 1);
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::Playing);
+    }
+
+    /// Press then release a key, one update each
+    fn tap_key(app: &mut App, key_code: KeyCode) {
+        for state in [ButtonState::Pressed, ButtonState::Released] {
+            app.world.send_event(KeyboardInput {
+                scan_code: 0,
+                key_code: Some(key_code),
+                state,
+                window: Entity::PLACEHOLDER,
+            });
+            app.update();
+        }
+    }
 
     #[test]
-    fn next_movement_out_of_game_area() {
+    fn rebind_screen_changes_the_keys_of_an_action() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, InputPlugin, StreetOfZombiesGameplay));
+        app.update();
+
+        tap_key(&mut app, KeyCode::C);
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::Controls);
+
+        // The first action of the rebind screen is selected
+        tap_key(&mut app, KeyCode::Return);
+        tap_key(&mut app, KeyCode::I);
+        tap_key(&mut app, KeyCode::Escape);
+
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::MainMenu);
+        assert_eq!(app.world.query::<&Player>().iter(&app.world).count(), 0);
         assert_eq!(
-            is_next_movement_out_of_game_area((GAME_AREA_LIMIT_X, GAME_AREA_LIMIT_Y), (1., 1.)),
-            true
+            app.world.resource::<InputBindings>().get_keys(InputAction::MoveUp),
+            &[KeyCode::I]
         );
     }
 
     #[test]
-    fn next_movement_inside_of_game_area() {
-        assert_eq!(is_next_movement_out_of_game_area((0., 0.), (0., 0.)), false);
+    fn unplugged_gamepad_pauses_the_game_until_it_is_plugged_back() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, InputPlugin, StreetOfZombiesGameplay));
+        start_game(&mut app);
+        app.update();
+
+        app.world.send_event(GamepadConnectionEvent::new(
+            Gamepad::new(0),
+            GamepadConnection::Disconnected,
+        ));
+        app.update();
+        app.update();
+
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::Paused);
+
+        app.world.send_event(GamepadConnectionEvent::new(
+            Gamepad::new(0),
+            GamepadConnection::Connected(GamepadInfo {
+                name: "Gamepad".to_string(),
+            }),
+        ));
+        app.update();
+        app.update();
+
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::Playing);
+    }
+
+    #[test]
+    fn plugged_gamepad_does_not_leave_the_pause_of_the_player() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, InputPlugin, StreetOfZombiesGameplay));
+        start_game(&mut app);
+        app.update();
+        app.world.resource_mut::<NextState<GameState>>().set(GameState::Paused);
+        app.update();
+
+        app.world.send_event(GamepadConnectionEvent::new(
+            Gamepad::new(0),
+            GamepadConnection::Connected(GamepadInfo {
+                name: "Gamepad".to_string(),
+            }),
+        ));
+        app.update();
+        app.update();
+
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::Paused);
+    }
+
+    #[test]
+    fn pause_freezes_the_gameplay() {
+        let mut app = App::new();
+        app.insert_resource(math_and_generator::GameRng::new(3))
+            .add_plugins((MinimalPlugins, InputPlugin, StreetOfZombiesGameplay))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+                1.0 / GAMEPLAY_TICKS_PER_SECOND,
+            )));
+        start_game(&mut app);
+
+        for _ in 0..600 {
+            app.update();
+        }
+
+        app.world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Paused);
+        app.update();
+        let paused_game = snapshot(&mut app);
+
+        for _ in 0..600 {
+            app.update();
+        }
+
+        assert_eq!(snapshot(&mut app), paused_game);
+        assert!(app.world.resource::<Time<Virtual>>().is_paused());
+    }
+
+    #[test]
+    fn gameplay_independent_of_frame_rate() {
+        let gameplay_tick = Duration::from_secs_f64(1.0 / GAMEPLAY_TICKS_PER_SECOND);
+        // 30 FPS: two gameplay ticks per frame
+        let ennemy_positions = simulate_twenty_seconds(gameplay_tick * 2);
+
+        assert!(!ennemy_positions.is_empty());
+        assert_eq!(
+            ennemy_positions,
+            simulate_twenty_seconds(Duration::from_secs_f64(1.0 / 144.0))
+        );
+    }
+
+    /// Positions of the player and of the ennemies, and the score
+    fn snapshot(app: &mut App) -> (Vec<Vec2>, u32) {
+        let mut position_query =
+            app.world.query_filtered::<&Transform, Or<(With<Player>, With<Ennemy>)>>();
+        let positions = position_query.iter(&app.world).map(get_position).collect();
+
+        (positions, app.world.resource::<ScoreAndInfo>().get_score())
+    }
+
+    #[test]
+    fn replay_reproduces_recorded_game() {
+        let gameplay_tick = Duration::from_secs_f64(1.0 / GAMEPLAY_TICKS_PER_SECOND);
+        let replay_path = std::env::temp_dir().join("street_of_zombies_replay_test.sozr");
+        let replay_path = replay_path.to_str().unwrap();
+
+        let mut recorded_app = App::new();
+        recorded_app
+            .insert_resource(math_and_generator::GameRng::new(7))
+            .insert_resource(ReplayRecorder::new(replay_path.to_string(), 7, GameConfig::default()))
+            .add_plugins((MinimalPlugins, InputPlugin, StreetOfZombiesGameplay))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(gameplay_tick));
+        start_game(&mut recorded_app);
+
+        recorded_app.world.resource_mut::<Input<KeyCode>>().press(KeyCode::Space);
+        for tick in 0..1200 {
+            let mut keyboard = recorded_app.world.resource_mut::<Input<KeyCode>>();
+            match tick % 300 {
+                0 => keyboard.press(KeyCode::Left),
+                100 => keyboard.release(KeyCode::Left),
+                150 => keyboard.press(KeyCode::Right),
+                _ => (),
+            }
+            if tick % 300 == 299 {
+                keyboard.release(KeyCode::Right);
+            }
+            recorded_app.update();
+        }
+
+        recorded_app.world.resource::<ReplayRecorder>().save().unwrap();
+        let replay = replay::Replay::load(replay_path).unwrap();
+        std::fs::remove_file(replay_path).unwrap();
+
+        let mut replayed_app = App::new();
+        replayed_app
+            .insert_resource(math_and_generator::GameRng::new(replay.get_seed()))
+            .insert_resource(replay.get_game_config().clone())
+            .insert_resource(ReplayPlayer::new(replay))
+            .add_plugins((MinimalPlugins, InputPlugin, StreetOfZombiesGameplay))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(gameplay_tick));
+
+        while !replayed_app.world.resource::<ReplayPlayer>().is_finished() {
+            replayed_app.update();
+        }
+
+        assert_eq!(snapshot(&mut recorded_app), snapshot(&mut replayed_app));
     }
 }
@@ -0,0 +1,415 @@
+use crate::game_entity::*;
+use crate::game_system::game_config::GameConfig;
+use crate::game_system::gameplay_events::ShotFired;
+use crate::game_system::input_bindings::{InputAction, InputBindings};
+use crate::weapons::FireResult;
+
+use bevy::input::mouse::MouseWheel;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+/// Minimal deflection of the right stick of the gamepad to aim
+static AIM_STICK_DEADZONE: f32 = 0.3;
+/// Minimal deflection of the left stick of the gamepad to move
+static MOVEMENT_STICK_DEADZONE: f32 = 0.2;
+
+/// A request of the player to take another weapon of its inventory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeaponSwitch {
+    /// Take the weapon of an inventory slot, from 0
+    Slot(u8),
+    Next,
+    Previous,
+}
+
+/// How the player chooses the direction of fire
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AimMode {
+    /// Fire in the direction of the movement
+    #[default]
+    Movement,
+    /// Fire toward the mouse cursor
+    Mouse,
+    /// Fire in the direction of the right stick of the gamepad
+    Gamepad,
+}
+
+impl AimMode {
+    /// Get the next aim mode, the aim modes are switched in a loop
+    pub fn get_next(&self) -> AimMode {
+        match self {
+            AimMode::Movement => AimMode::Mouse,
+            AimMode::Mouse => AimMode::Gamepad,
+            AimMode::Gamepad => AimMode::Movement,
+        }
+    }
+
+    /// Get the name of the aim mode, printed on the HUD
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            AimMode::Movement => "MOVEMENT",
+            AimMode::Mouse => "MOUSE",
+            AimMode::Gamepad => "GAMEPAD",
+        }
+    }
+}
+
+/// The commands of the player, captured once per frame and applied at each gameplay tick
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq)]
+pub struct PlayerInput {
+    pub left: bool,
+    pub right: bool,
+    pub up: bool,
+    pub down: bool,
+    /// True while the fire button is held
+    pub fire: bool,
+    /// True while the reload button is held
+    pub reload: bool,
+    /// True when the player asked to restart the game (kept until the next gameplay tick)
+    pub restart: bool,
+    /// The weapon switch asked by the player (kept until the next gameplay tick)
+    pub weapon_switch: Option<WeaponSwitch>,
+    /// The direction of fire chosen with the mouse or the gamepad, None to fire in the direction of the movement
+    pub aim: Option<Vec2>,
+    /// The analog movement of the left stick of the gamepad, its length is the fraction of the speed of the player.
+    /// None to move with the movement keys
+    pub movement: Option<Vec2>,
+}
+
+impl PlayerInput {
+    /// Get the movement direction following the left stick of the gamepad, or the movement keys.
+    /// Zero when the player does not move
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///     # use bevy::prelude::*;
+    ///     # use street_of_zombies::game_system::player_input::PlayerInput;
+    ///     let player_input = PlayerInput { up: true, ..Default::default() };
+    ///     assert_eq!(player_input.get_direction(), Vec2::new(0.0, 1.0));
+    /// ```
+    pub fn get_direction(&self) -> Vec2 {
+        if let Some(movement) = self.movement {
+            return movement;
+        }
+
+        let mut direction = Vec2::ZERO;
+        let mut number_of_valid_pressure: u8 = 0;
+
+        if self.left {
+            direction.x = -1.0;
+            number_of_valid_pressure += 1;
+        }
+        if self.right {
+            direction.x = 1.0;
+            number_of_valid_pressure += 1;
+        }
+        if self.up {
+            direction.y = 1.0;
+            number_of_valid_pressure += 1;
+        }
+        if self.down {
+            direction.y = -1.0;
+            number_of_valid_pressure += 1;
+        }
+
+        if number_of_valid_pressure > 1 {
+            direction = (direction.abs() - 0.33) * direction;
+        }
+
+        direction
+    }
+}
+
+/// Capture the actions bound to the keyboard (and the mouse buttons) to move, fire or switch weapon with the player entity.
+/// Managed by as a "Bevy System"
+pub fn keyboard_capture(
+    keyboard_input: Res<Input<KeyCode>>,
+    input_bindings: Res<InputBindings>,
+    mouse_input: Res<Input<MouseButton>>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    mut player_input: ResMut<PlayerInput>,
+    mut aim_mode: ResMut<AimMode>,
+) {
+    let is_pressed = |action| input_bindings.pressed(&keyboard_input, action);
+    let is_just_pressed = |action| input_bindings.just_pressed(&keyboard_input, action);
+
+    player_input.fire = is_pressed(InputAction::Fire) || mouse_input.pressed(MouseButton::Left);
+    player_input.reload = is_pressed(InputAction::Reload);
+    player_input.left = is_pressed(InputAction::MoveLeft);
+    player_input.right = is_pressed(InputAction::MoveRight);
+    player_input.up = is_pressed(InputAction::MoveUp);
+    player_input.down = is_pressed(InputAction::MoveDown);
+
+    if is_just_pressed(InputAction::Restart) {
+        player_input.restart = true;
+    }
+
+    for (slot, action) in InputAction::WEAPON_SLOTS.iter().enumerate() {
+        if is_just_pressed(*action) {
+            player_input.weapon_switch = Some(WeaponSwitch::Slot(slot as u8));
+        }
+    }
+
+    if is_just_pressed(InputAction::ChangeAimMode) {
+        *aim_mode = aim_mode.get_next();
+    }
+
+    let wheel_movement: f32 = mouse_wheel_events.read().map(|event| event.y).sum();
+    if wheel_movement > 0.0 || is_just_pressed(InputAction::NextWeapon) {
+        player_input.weapon_switch = Some(WeaponSwitch::Next);
+    } else if wheel_movement < 0.0 || is_just_pressed(InputAction::PreviousWeapon) {
+        player_input.weapon_switch = Some(WeaponSwitch::Previous);
+    }
+}
+
+/// Capture the gamepad entry to move (left stick), fire (right trigger or South button), reload (West button),
+/// switch weapon (bumpers) or restart (Start button). Every connected gamepad controls the player.
+/// Managed by as a "Bevy System"
+pub fn gamepad_capture(
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    mut player_input: ResMut<PlayerInput>,
+) {
+    player_input.movement = None;
+
+    for gamepad in gamepads.iter() {
+        let is_pressed = |button_type| gamepad_buttons.pressed(GamepadButton::new(gamepad, button_type));
+        let is_just_pressed =
+            |button_type| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button_type));
+
+        player_input.fire |= is_pressed(GamepadButtonType::RightTrigger2) || is_pressed(GamepadButtonType::South);
+        player_input.reload |= is_pressed(GamepadButtonType::West);
+
+        if is_just_pressed(GamepadButtonType::RightTrigger) {
+            player_input.weapon_switch = Some(WeaponSwitch::Next);
+        } else if is_just_pressed(GamepadButtonType::LeftTrigger) {
+            player_input.weapon_switch = Some(WeaponSwitch::Previous);
+        }
+
+        if is_just_pressed(GamepadButtonType::Start) {
+            player_input.restart = true;
+        }
+
+        if player_input.movement.is_none() {
+            let stick = Vec2::new(
+                gamepad_axes
+                    .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+                    .unwrap_or(0.0),
+                gamepad_axes
+                    .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY))
+                    .unwrap_or(0.0),
+            );
+            player_input.movement = get_stick_movement(stick);
+        }
+    }
+}
+
+/// Return true if the button is just pressed on any connected gamepad
+///
+/// # Arguments
+///
+/// * `gamepads` - The connected gamepads
+/// * `gamepad_buttons` - The state of the buttons of the gamepads
+/// * `button_type` - The button to check
+///
+pub fn is_gamepad_button_just_pressed(
+    gamepads: &Gamepads,
+    gamepad_buttons: &Input<GamepadButton>,
+    button_type: GamepadButtonType,
+) -> bool {
+    gamepads
+        .iter()
+        .any(|gamepad| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button_type)))
+}
+
+/// Convert the deflection of the left stick to an analog movement. None inside the deadzone.
+/// The length of the movement is at most 1, the player runs at full speed when the stick is fully deflected
+///
+/// # Arguments
+///
+/// * `stick` - The deflection of the stick, from -1 to 1
+///
+/// # Examples
+///
+/// ```ignore
+///     assert_eq!(get_stick_movement(Vec2::new(0., 0.5)), Some(Vec2::new(0., 0.5)));
+/// ```
+fn get_stick_movement(stick: Vec2) -> Option<Vec2> {
+    if stick.length() <= MOVEMENT_STICK_DEADZONE {
+        return None;
+    }
+
+    Some(stick.clamp_length_max(1.0))
+}
+
+/// Capture the direction of fire following the aim mode: toward the cursor (converted to the coordinates of the game area
+/// by the camera), or the direction of the right stick of the first gamepad. Managed by as a "Bevy System"
+#[allow(clippy::too_many_arguments)]
+pub fn aim_capture(
+    aim_mode: Res<AimMode>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    player_query: Query<&Transform, With<player::Player>>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    mut player_input: ResMut<PlayerInput>,
+) {
+    player_input.aim = match *aim_mode {
+        AimMode::Movement => None,
+        AimMode::Mouse => {
+            let cursor_position = window_query
+                .get_single()
+                .ok()
+                .and_then(|window| window.cursor_position());
+            let aim_target = match (cursor_position, camera_query.get_single()) {
+                (Some(cursor_position), Ok((camera, camera_transform))) => {
+                    camera.viewport_to_world_2d(camera_transform, cursor_position)
+                }
+                _ => None,
+            };
+
+            match (aim_target, player_query.get_single()) {
+                (Some(aim_target), Ok(player_transform)) => {
+                    get_aim_direction(aim_target - get_position(player_transform), 0.0)
+                }
+                _ => None,
+            }
+        }
+        AimMode::Gamepad => gamepads.iter().next().and_then(|gamepad| {
+            let stick = Vec2::new(
+                gamepad_axes
+                    .get(GamepadAxis::new(gamepad, GamepadAxisType::RightStickX))
+                    .unwrap_or(0.0),
+                gamepad_axes
+                    .get(GamepadAxis::new(gamepad, GamepadAxisType::RightStickY))
+                    .unwrap_or(0.0),
+            );
+
+            get_aim_direction(stick, AIM_STICK_DEADZONE)
+        }),
+    };
+}
+
+/// Convert an aim vector to a direction of fire of length 1. None if the vector is too short
+///
+/// # Arguments
+///
+/// * `aim_vector` - From the player to the target, or the deflection of the stick
+/// * `minimal_length` - The aim vectors shorter than this length are ignored
+///
+/// # Examples
+///
+/// ```ignore
+///     assert_eq!(get_aim_direction(Vec2::new(0., 30.), 0.), Some(Vec2::new(0., 1.)));
+/// ```
+fn get_aim_direction(aim_vector: Vec2, minimal_length: f32) -> Option<Vec2> {
+    if aim_vector.length() <= minimal_length {
+        return None;
+    }
+
+    aim_vector.try_normalize()
+}
+
+/// Game System: Move the player and fire with its weapon following the captured input. Managed by as a "Bevy System"
+pub fn player_control_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    player_input: Res<PlayerInput>,
+    mut query: Query<(&mut player::Player, &mut Transform)>,
+    mut shot_fired_events: EventWriter<ShotFired>,
+    game_config: Res<GameConfig>,
+) {
+    if let Ok((mut player, mut transform)) = query.get_single_mut() {
+        player.set_aim_direction(player_input.aim);
+
+        match player_input.weapon_switch {
+            Some(WeaponSwitch::Slot(slot)) => player.select_weapon(slot as usize),
+            Some(WeaponSwitch::Next) => player.select_next_weapon(),
+            Some(WeaponSwitch::Previous) => player.select_previous_weapon(),
+            None => (),
+        }
+
+        if player_input.reload {
+            player.reload_weapon();
+        }
+        if player_input.fire && player.fire(&mut commands, &time, get_position(&transform)) == FireResult::Fired {
+            shot_fired_events.send(ShotFired { is_from_ennemy: false });
+        }
+
+        let direction = player_input.get_direction();
+        if direction == Vec2::ZERO {
+            // The ennemies aim with the last movement of the player
+            player.get_moveable_interface_mut().stop(&transform);
+            return;
+        }
+
+        player.get_moveable_interface_mut().move_sprite(
+            &time,
+            direction,
+            &mut transform,
+            &game_config.game_area,
+        );
+    }
+}
+
+/// Game System: The restart and weapon switch requests are consumed by the gameplay tick which received them. Managed by as a "Bevy System"
+pub fn clear_requests_system(mut player_input: ResMut<PlayerInput>) {
+    player_input.restart = false;
+    player_input.weapon_switch = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aim_direction_has_a_length_of_one() {
+        assert_eq!(get_aim_direction(Vec2::new(0., 30.), 0.), Some(Vec2::new(0., 1.)));
+        assert_eq!(get_aim_direction(Vec2::new(-0.6, 0.8), 0.3), Some(Vec2::new(-0.6, 0.8)));
+        // Inside the deadzone of the stick, or on the player
+        assert_eq!(get_aim_direction(Vec2::new(0.1, 0.2), 0.3), None);
+        assert_eq!(get_aim_direction(Vec2::ZERO, 0.), None);
+    }
+
+    #[test]
+    fn stick_movement_is_analog() {
+        assert_eq!(get_stick_movement(Vec2::new(0., 0.5)), Some(Vec2::new(0., 0.5)));
+        assert_eq!(get_stick_movement(Vec2::new(0.1, -0.1)), None);
+        // Some gamepads go further than 1 in the diagonals
+        let movement = get_stick_movement(Vec2::new(1., 1.)).unwrap();
+        assert!((movement.length() - 1.).abs() < 0.0001);
+
+        let player_input = PlayerInput {
+            left: true,
+            movement: Some(Vec2::new(0.3, 0.4)),
+            ..Default::default()
+        };
+        assert_eq!(player_input.get_direction(), Vec2::new(0.3, 0.4));
+    }
+
+    #[test]
+    fn no_key_no_direction() {
+        assert_eq!(PlayerInput::default().get_direction(), Vec2::ZERO);
+    }
+
+    #[test]
+    fn single_key_direction() {
+        let player_input = PlayerInput {
+            left: true,
+            ..Default::default()
+        };
+        assert_eq!(player_input.get_direction(), Vec2::new(-1.0, 0.0));
+    }
+
+    #[test]
+    fn diagonal_direction() {
+        let player_input = PlayerInput {
+            right: true,
+            down: true,
+            ..Default::default()
+        };
+        assert_eq!(player_input.get_direction(), Vec2::new(1.0 - 0.33, -(1.0 - 0.33)));
+    }
+}
@@ -1,6 +1,10 @@
-use bevy::{prelude::*, sprite::collide_aabb::collide};
+use bevy::prelude::*;
 
+use crate::game_entity::health::Health;
 use crate::game_entity::*;
+use crate::game_system::game_config::GameConfig;
+use crate::game_system::gameplay_events::{EnnemyHit, EnnemyKilled, PlayerHit};
+use crate::game_system::spatial_grid::SpatialGrid;
 use crate::game_system::*;
 
 /// Game System: Automatic movement of the projectiles. Managed by as a "Bevy System"
@@ -8,207 +12,755 @@ pub fn projectile_movement_system(
     mut commands: Commands,
     time: Res<Time>,
     mut projectile_query: Query<(&mut projectiles::Projectile, &mut Transform, Entity)>,
+    game_config: Res<GameConfig>,
 ) {
     for projectile_single_query in projectile_query.iter_mut() {
         let (mut projectile, mut transform, projectile_entity) = projectile_single_query;
         let direction_of_fire = projectile.get_moveable_interface().get_direction();
         projectile.get_moveable_interface_mut().move_sprite(
             &time,
-            &direction_of_fire,
-            &mut transform.translation,
+            direction_of_fire,
+            &mut transform,
+            &game_config.game_area,
         );
+        let position = get_position(&transform);
 
         // If outside of game area, delete
-        if (projectile.is_out_of_distance())
-            || is_next_movement_out_of_game_area(
-                projectile.get_moveable_interface().get_position(),
-                projectile.get_moveable_interface().get_direction(),
-            )
+        if (projectile.is_out_of_distance(position))
+            || is_next_movement_out_of_game_area(position, direction_of_fire, &game_config.game_area)
         {
             commands.entity(projectile_entity).despawn();
         }
     }
 }
 
-/// Game System: The collision system with projectiles and manage the part "health + Score" of the score system. Managed by as a "Bevy System"
-pub fn projectile_collision_and_score_system(
+/// The ennemies, with their health and their position
+type EnnemyQuery<'world, 'state, 'ennemy> = Query<
+    'world,
+    'state,
+    (&'ennemy ennemies::Ennemy, &'ennemy mut Health, &'ennemy Transform, Entity),
+    Without<player::Player>,
+>;
+
+/// The player, with its health and its position
+type PlayerQuery<'world, 'state, 'player> =
+    Query<'world, 'state, (&'player mut player::Player, &'player mut Health, &'player Transform, Entity)>;
+
+/// Game System: The collision system with projectiles, each hit sends `EnnemyHit` or `PlayerHit`.
+/// The projectiles of the player are only checked against the ennemies of the `SpatialGrid` cells crossed by their last movement.
+/// The ennemies on the path of a projectile are hit in the order the projectile reaches them.
+/// The dead are treated by the `death_system`, the score by the listeners. Managed by as a "Bevy System"
+pub fn projectile_collision_system(
     mut commands: Commands,
-    mut enemy_query: Query<(&mut ennemies::Ennemy, Entity)>,
-    mut player_query: Query<(&mut player::Player, Entity)>,
-    projectile_query: Query<(Entity, &projectiles::Projectile)>,
-    mut scoreboard_query: Query<(&mut scoreboard::ScoreAndInfo, &mut Text, &mut Style)>,
+    mut ennemy_hit_events: EventWriter<EnnemyHit>,
+    mut player_hit_events: EventWriter<PlayerHit>,
+    spatial_grid: Res<SpatialGrid>,
+    mut enemy_query: EnnemyQuery,
+    mut player_query: PlayerQuery,
+    mut projectile_query: Query<(Entity, &mut projectiles::Projectile, &Transform)>,
 ) {
-    if let Ok((mut score_struct, mut score_text, mut style_text)) = scoreboard_query.get_single_mut() {
-
     // check collision with objects
-    for (collider_entity, projectile) in projectile_query.iter() {
+    for (collider_entity, mut projectile, projectile_transform) in projectile_query.iter_mut() {
         if projectile.is_coming_from_ennemy() {
             check_collision_with_player(
                 &mut commands,
                 &mut player_query,
-                projectile,
+                &mut projectile,
+                projectile_transform,
                 &collider_entity,
-                &mut score_struct,
+                &mut player_hit_events,
             );
         } else {
             check_collision_with_ennemy(
                 &mut commands,
+                &spatial_grid,
                 &mut enemy_query,
-                projectile,
+                &mut projectile,
+                projectile_transform,
                 &collider_entity,
-                &mut score_struct,
+                &mut ennemy_hit_events,
             );
         }
     }
+}
+
+/// Game System: The ennemies hurt the player on contact. The player is pushed back, away from the ennemy,
+/// and cannot be hurt again during its invulnerability. Managed by as a "Bevy System"
+pub fn ennemy_contact_damage_system(
+    mut player_hit_events: EventWriter<PlayerHit>,
+    spatial_grid: Res<SpatialGrid>,
+    ennemy_query: Query<(&ennemies::Ennemy, &Health, &Transform), Without<player::Player>>,
+    mut player_query: Query<(&mut player::Player, &mut Health, &mut Transform)>,
+    game_config: Res<GameConfig>,
+) {
+    for (mut player, mut health, mut player_transform) in player_query.iter_mut() {
+        if player.is_invulnerable() || health.is_dead() {
+            continue;
+        }
+
+        let player_position = get_position(&player_transform);
+        let player_hitbox = player.get_moveable_interface().get_hitbox_size();
+        let touching_ennemy = spatial_grid
+            .get_candidates(player_position, player_hitbox)
+            .into_iter()
+            .filter_map(|candidate| ennemy_query.get(candidate).ok())
+            .find(|(ennemy, ennemy_health, ennemy_transform)| {
+                // An ennemy killed during this tick is still in the query
+                !ennemy_health.is_dead()
+                    && is_entities_collides(
+                        (ennemy_transform, ennemy.get_moveable_interface()),
+                        (&player_transform, player.get_moveable_interface()),
+                    )
+            });
+
+        let Some((ennemy, _, ennemy_transform)) = touching_ennemy else {
+            continue;
+        };
 
-        score_struct.update_percent_until_next_level();
-        score_struct.update_scoarboard_text(&mut score_text, &mut style_text);
+        hurt_player(&mut player, &mut health, ennemy.get_contact_damage(), &mut player_hit_events);
+
+        let knockback_direction = (player_position - get_position(ennemy_transform))
+            .try_normalize()
+            .unwrap_or(-player.get_fire_direction());
+        player.get_moveable_interface().push_sprite(
+            knockback_direction * game_config.player.knockback_distance,
+            &mut player_transform,
+            &game_config.game_area,
+        );
+    }
+}
+
+/// Game System: The killed ennemies with a death explosion hurt the player in its radius. Managed by as a "Bevy System"
+pub fn ennemy_death_explosion_system(
+    mut killed_events: EventReader<EnnemyKilled>,
+    mut player_hit_events: EventWriter<PlayerHit>,
+    mut player_query: Query<(&mut player::Player, &mut Health, &Transform)>,
+    game_config: Res<GameConfig>,
+) {
+    for killed_ennemy in killed_events.read() {
+        let Some(death_explosion) = &game_config.ennemy.get(killed_ennemy.kind).death_explosion else {
+            continue;
+        };
+
+        for (mut player, mut health, player_transform) in player_query.iter_mut() {
+            let distance_from_explosion = killed_ennemy.position.distance(get_position(player_transform));
+
+            if !player.is_invulnerable() && !health.is_dead() && distance_from_explosion <= death_explosion.radius {
+                hurt_player(&mut player, &mut health, death_explosion.damage, &mut player_hit_events);
+            }
+        }
     }
 }
 
 fn check_collision_with_ennemy(
     commands: &mut Commands,
-    entity_query: &mut Query<(&mut ennemies::Ennemy, Entity)>,
-    projectile: &projectiles::Projectile,
+    spatial_grid: &SpatialGrid,
+    entity_query: &mut EnnemyQuery,
+    projectile: &mut projectiles::Projectile,
+    projectile_transform: &Transform,
     projectile_entity: &Entity,
-    score_struct: &mut scoreboard::ScoreAndInfo,
+    ennemy_hit_events: &mut EventWriter<EnnemyHit>,
 ) {
-    for (mut ennemy, entity_ennemy) in entity_query.iter_mut() {
-        let sprite_interface_one = ennemy.get_moveable_interface();
-        let sprite_interface_two = projectile.get_moveable_interface();
+    // Until the despawn command is applied, a spent projectile stays in the query
+    if projectile.is_spent() {
+        return;
+    }
+    let (swept_center, swept_size) = get_swept_hitbox(projectile_transform, projectile.get_moveable_interface());
+
+    // The ennemies are hit in the order the projectile reaches them, not in the order of the spatial grid
+    let mut ennemies_on_path: Vec<(f32, Entity)> = spatial_grid
+        .get_candidates(swept_center, swept_size)
+        .into_iter()
+        .filter_map(|candidate| {
+            let (ennemy, health, ennemy_transform, _) = entity_query.get(candidate).ok()?;
+            // An ennemy killed during this tick is still in the query
+            if health.is_dead() {
+                return None;
+            }
+
+            get_collision_time(
+                (ennemy_transform, ennemy.get_moveable_interface()),
+                (projectile_transform, projectile.get_moveable_interface()),
+            )
+            .map(|time_of_impact| (time_of_impact, candidate))
+        })
+        .collect();
+    ennemies_on_path.sort_by(|first_hit, second_hit| first_hit.0.total_cmp(&second_hit.0));
+
+    for (time_of_impact, candidate) in ennemies_on_path {
+        let Ok((ennemy, mut health, _, entity_ennemy)) = entity_query.get_mut(candidate) else {
+            continue;
+        };
+        if !projectile.register_hit(entity_ennemy) {
+            continue;
+        }
 
-        if is_entities_collides(&sprite_interface_one, sprite_interface_two) {
+        if let Some(explosion_radius) = projectile.get_explosion_radius() {
             commands.entity(*projectile_entity).despawn();
-            ennemy.reduce_health();
-            score_struct.add_to_score(ennemy.get_point_value_per_hits());
-            check_and_treat_ennemy_health(commands, &mut ennemy, entity_ennemy, score_struct);
+            explode(
+                spatial_grid,
+                entity_query,
+                get_position_at_time(projectile_transform, projectile.get_moveable_interface(), time_of_impact),
+                explosion_radius,
+                projectile.get_damage(),
+                ennemy_hit_events,
+            );
+            return;
+        }
+
+        hit_ennemy(ennemy, &mut health, entity_ennemy, projectile.get_damage(), ennemy_hit_events);
+
+        if projectile.is_spent() {
+            commands.entity(*projectile_entity).despawn();
+            return;
         }
     }
 }
 
-fn check_collision_with_player(
-    commands: &mut Commands,
-    entity_query: &mut Query<(&mut player::Player, Entity)>,
-    projectile: &projectiles::Projectile,
-    projectile_entity: &Entity,
-    score_struct: &mut scoreboard::ScoreAndInfo,
+/// Hit every ennemy in the radius of an explosion
+///
+/// # Arguments
+///
+/// * `spatial_grid` - The ennemies close to each position.
+/// * `entity_query` - The ennemies.
+/// * `impact_position` - The position of the explosion
+/// * `explosion_radius` - The radius of the explosion
+/// * `damage` - The damage of the explosion on each ennemy
+/// * `ennemy_hit_events` - The events sent for each ennemy hurt
+///
+fn explode(
+    spatial_grid: &SpatialGrid,
+    entity_query: &mut EnnemyQuery,
+    impact_position: Vec2,
+    explosion_radius: f32,
+    damage: u32,
+    ennemy_hit_events: &mut EventWriter<EnnemyHit>,
 ) {
-    for (player, entity_player) in entity_query.iter_mut() {
-        let sprite_interface_one = player.get_moveable_interface();
-        let sprite_interface_two = projectile.get_moveable_interface();
+    let explosion_size = Vec2::splat(2. * explosion_radius);
 
-        if is_entities_collides(&sprite_interface_one, sprite_interface_two) {
-            commands.entity(*projectile_entity).despawn();
-            score_struct.remove_health(1);
-            check_and_treat_player_health(commands, entity_player, score_struct);
+    for candidate in spatial_grid.get_candidates(impact_position, explosion_size) {
+        let Ok((ennemy, mut health, ennemy_transform, entity_ennemy)) = entity_query.get_mut(candidate) else {
+            continue;
+        };
+        let distance_from_impact = impact_position.distance(get_position(ennemy_transform));
+
+        if !health.is_dead() && distance_from_impact <= explosion_radius {
+            hit_ennemy(ennemy, &mut health, entity_ennemy, damage, ennemy_hit_events);
         }
     }
 }
 
-fn check_and_treat_ennemy_health(
-    commands: &mut Commands,
-    ennemy: &mut ennemies::Ennemy,
-    entity: Entity,
-    score: &mut scoreboard::ScoreAndInfo,
+/// Hurt an ennemy, the hit gives its points only when some health is removed
+fn hit_ennemy(
+    ennemy: &ennemies::Ennemy,
+    health: &mut Health,
+    entity_ennemy: Entity,
+    damage: u32,
+    ennemy_hit_events: &mut EventWriter<EnnemyHit>,
 ) {
-    if ennemy.is_dead() {
-        score.add_to_score(ennemy.get_point_value_on_death());
-        commands.entity(entity).despawn();
+    if health.take_damage(damage) > 0 {
+        ennemy_hit_events.send(EnnemyHit {
+            entity: entity_ennemy,
+            points: ennemy.get_point_value_per_hits(),
+        });
     }
 }
 
-fn check_and_treat_player_health(
+/// An invulnerable player is not hurt: the projectile goes through it
+fn check_collision_with_player(
     commands: &mut Commands,
-    player_entity: Entity,
-    score: &mut scoreboard::ScoreAndInfo,
+    entity_query: &mut PlayerQuery,
+    projectile: &mut projectiles::Projectile,
+    projectile_transform: &Transform,
+    projectile_entity: &Entity,
+    player_hit_events: &mut EventWriter<PlayerHit>,
 ) {
-    if score.is_gameover() {
-        commands.entity(player_entity).despawn();
+    for (mut player, mut health, player_transform, entity_player) in entity_query.iter_mut() {
+        if !player.is_invulnerable()
+            && !health.is_dead()
+            && is_entities_collides(
+                (player_transform, player.get_moveable_interface()),
+                (projectile_transform, projectile.get_moveable_interface()),
+            )
+            && projectile.register_hit(entity_player)
+        {
+            hurt_player(&mut player, &mut health, projectile.get_damage(), player_hit_events);
+
+            if projectile.is_spent() {
+                commands.entity(*projectile_entity).despawn();
+            }
+        }
     }
 }
 
-/// Retrieve the position and the hitbox converted in "Bevy" format from a "Moveable Sprite"
-/// The format retrieved is a Tuple ((Position_x, Position_y, 0.), (Hibox_x, Hitbox_y))
+/// Hurt the player, who cannot be hurt again during its invulnerability
+fn hurt_player(
+    player: &mut player::Player,
+    health: &mut Health,
+    damage: u32,
+    player_hit_events: &mut EventWriter<PlayerHit>,
+) {
+    let health_removed = health.take_damage(damage);
+    if health_removed > 0 {
+        player_hit_events.send(PlayerHit { damage: health_removed });
+    }
+    player.start_invulnerability();
+}
+
+/// Return true if the two entities collides
+/// It means that there is a collision between the hit-boxes of the MoveableSprite, centered on the position of each entity.
+/// The second entity is swept along its last movement: a fast entity cannot go through the first one between two ticks.
+///
+/// # Arguments
+///
+/// * `first_entity` - The transform and the moveable sprite of the first entity
+/// * `second_entity` - The transform and the moveable sprite of the second entity, the moving one
 ///
 /// # Examples
 ///
+/// ```ignore
+///    let projectile = Projectile::new(500.0, Vec2::new(5., 10.), Vec2::new(15., 20.), 500, false);
+///    let ennemy = ennemies::Ennemy::new(&EnnemyConfig::default(), ennemies::EnnemyKind::Walker, Vec2::new(5., 10.), Vec2::new(15., 20.));
+///    let transform = Transform::from_xyz(15., 20., 0.);
+///    assert_eq!(
+///        is_entities_collides(
+///            (&transform, ennemy.get_moveable_interface()),
+///            (&transform, projectile.get_moveable_interface())
+///        ),
+///        true
+///    );
 /// ```
-///    let projectile = Projectile::new(500.0, (5., 10.), (15., 20.), 500, false);
-///    let (projectile_position, projectile_hitbox) = get_position_and_hitboxes(&projectile);
-/// ```
-fn get_position_and_hitboxes(entity: &MoveableSprite) -> (Vec3, Vec2) {
-    let position = entity.get_position();
-    let hibox_size = entity.get_hitbox_size();
+pub(crate) fn is_entities_collides(
+    first_entity: (&Transform, &MoveableSprite),
+    second_entity: (&Transform, &MoveableSprite),
+) -> bool {
+    get_collision_time(first_entity, second_entity).is_some()
+}
 
-    (
-        Vec3::new(position.0, position.1, 0.),
-        Vec2::new(hibox_size.0, hibox_size.1),
-    )
+/// Get the moment of the last movement of the second entity when it touched the first one, None if they do not collide.
+/// 0. is the position before the movement, 1. is the current position.
+///
+/// The hitbox of the second entity is swept from its previous position to its current one, against the hitbox of
+/// the first entity at its current position (a ray against the box enlarged by the second hitbox).
+///
+/// # Arguments
+///
+/// * `first_entity` - The transform and the moveable sprite of the first entity
+/// * `second_entity` - The transform and the moveable sprite of the second entity, the moving one
+///
+fn get_collision_time(
+    first_entity: (&Transform, &MoveableSprite),
+    second_entity: (&Transform, &MoveableSprite),
+) -> Option<f32> {
+    let (first_transform, first_sprite) = first_entity;
+    let (second_transform, second_sprite) = second_entity;
+
+    let box_center = get_position(first_transform);
+    let box_half_size = (first_sprite.get_hitbox_size() + second_sprite.get_hitbox_size()) / 2.;
+    let end_position = get_position(second_transform);
+    let start_position = second_sprite.get_previous_position().unwrap_or(end_position);
+    let movement = end_position - start_position;
+
+    let mut time_of_entry: f32 = 0.;
+    let mut time_of_exit: f32 = 1.;
+
+    for axis in 0..2 {
+        let box_min = box_center[axis] - box_half_size[axis];
+        let box_max = box_center[axis] + box_half_size[axis];
+
+        if movement[axis] == 0. {
+            // Touching edges is not a collision
+            if start_position[axis] <= box_min || start_position[axis] >= box_max {
+                return None;
+            }
+        } else {
+            let time_at_min = (box_min - start_position[axis]) / movement[axis];
+            let time_at_max = (box_max - start_position[axis]) / movement[axis];
+            time_of_entry = time_of_entry.max(time_at_min.min(time_at_max));
+            time_of_exit = time_of_exit.min(time_at_min.max(time_at_max));
+        }
+    }
+
+    (time_of_entry < time_of_exit).then_some(time_of_entry)
 }
 
-/// Return true if the two entities collides
-/// It means that there is a collision between the position and hit-boxes of the MoveableSprite.
+/// Get the position of an entity during its last movement
 ///
-/// # Examples
+/// # Arguments
 ///
-/// ```
-///    let projectile = Projectile::new(500.0, (5., 10.), (15., 20.), 500, false);
-///    let ennemy = ennemies::Ennemy::new(500.0, (5., 10.), (15., 20.), (25., 30.), 50);
-///    assert_eq!(is_entities_collides(&ennemy, &projectile), true);
-/// ```
-fn is_entities_collides(first_entity: &MoveableSprite, second_entity: &MoveableSprite) -> bool {
-    let (position_1, hitbox_1) = get_position_and_hitboxes(first_entity);
-    let (position_2, hitbox_2) = get_position_and_hitboxes(second_entity);
+/// * `transform` - The transform of the entity, its current position
+/// * `moveable_sprite` - The moveable sprite of the entity
+/// * `time` - 0. for the position before the movement, 1. for the current position
+///
+fn get_position_at_time(transform: &Transform, moveable_sprite: &MoveableSprite, time: f32) -> Vec2 {
+    let end_position = get_position(transform);
+    let start_position = moveable_sprite.get_previous_position().unwrap_or(end_position);
+
+    start_position.lerp(end_position, time)
+}
 
-    let collision = collide(position_1, hitbox_1, position_2, hitbox_2);
+/// Get the box covering the hitbox of an entity along its whole last movement, as a center and a size
+///
+/// # Arguments
+///
+/// * `transform` - The transform of the entity, its current position
+/// * `moveable_sprite` - The moveable sprite of the entity
+///
+fn get_swept_hitbox(transform: &Transform, moveable_sprite: &MoveableSprite) -> (Vec2, Vec2) {
+    let end_position = get_position(transform);
+    let start_position = moveable_sprite.get_previous_position().unwrap_or(end_position);
 
-    collision.is_some()
+    (
+        (start_position + end_position) / 2.,
+        moveable_sprite.get_hitbox_size() + (end_position - start_position).abs(),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game_system::game_config::{EnnemyConfig, GameAreaConfig, PlayerConfig, WeaponsConfig};
+    use bevy::ecs::system::RunSystemOnce;
 
     #[test]
-    fn get_position_from_moveable_sprite() {
-        let ennemy = ennemies::Ennemy::new(500.0, (5., 10.), (15., 20.), 50);
-        let (ennemy_position, _) = get_position_and_hitboxes(ennemy.get_moveable_interface());
+    fn two_moveable_sprite_collides() {
+        let ennemy = ennemies::Ennemy::new(&EnnemyConfig::default(), ennemies::EnnemyKind::Walker, Vec2::new(5., 10.), Vec2::new(15., 20.));
+        let player = player::Player::new(&PlayerConfig::default(), &WeaponsConfig::default());
 
-        assert_eq!(ennemy_position, Vec3::new(15., 20., 0.));
+        assert!(is_entities_collides(
+            (&Transform::from_xyz(15., 20., 0.), ennemy.get_moveable_interface()),
+            (&Transform::from_xyz(30., 40., 0.), player.get_moveable_interface())
+        ));
     }
 
     #[test]
-    fn get_hitbox_from_moveable_sprite() {
-        let ennemy = ennemies::Ennemy::new(500.0, (5., 10.), (15., 20.), 50);
-        let (_, ennemy_hitbox) = get_position_and_hitboxes(ennemy.get_moveable_interface());
-        let ennemy_hibox = ennemy.get_moveable_interface().get_hitbox_size();
+    fn two_moveable_sprite_not_collides() {
+        let ennemy = ennemies::Ennemy::new(&EnnemyConfig::default(), ennemies::EnnemyKind::Walker, Vec2::new(5., 10.), Vec2::new(15., 20.));
+        let projectile =
+            projectiles::Projectile::new(500.0, Vec2::new(5., 10.), Vec2::new(150., 2000.), 500, false);
 
-        assert_eq!(ennemy_hitbox, Vec2::new(ennemy_hibox.0, ennemy_hibox.1));
+        assert!(!is_entities_collides(
+            (&Transform::from_xyz(15., 20., 0.), ennemy.get_moveable_interface()),
+            (&Transform::from_xyz(150., 2000., 0.), projectile.get_moveable_interface())
+        ));
     }
 
-    /*
-    TODO: Create a convienient test for this case.
-    --- This test doesn't run... No clues why (Bevy engine? Integration issue? However, work correctly during gameplay) ---
     #[test]
-    fn two_moveable_sprite_collides() {
-        let ennemy = ennemies::Ennemy::new(500.0, (5., 10.), (15., 20.), (25., 30.), 50);
-        let player = player::Player::new(500.0, (5., 10.), (15., 20.));
+    fn fast_projectile_does_not_go_through_the_ennemy() {
+        let mut world = World::new();
+        let mut time = Time::<()>::default();
+        // A long frame: the projectile moves 500 px in a single tick
+        time.advance_by(std::time::Duration::from_secs(1));
+        world.insert_resource(time);
+
+        world.run_system_once(|time: Res<Time>| {
+            let ennemy = ennemies::Ennemy::new(&EnnemyConfig::default(), ennemies::EnnemyKind::Walker, Vec2::new(0., 1.), Vec2::ZERO);
+            let mut projectile =
+                projectiles::Projectile::new(500.0, Vec2::new(0., 1.), Vec2::new(0., -250.), 900, false);
+            let mut projectile_transform = Transform::from_xyz(0., -250., 0.);
+            projectile.get_moveable_interface_mut().move_sprite(
+                &time,
+                Vec2::new(0., 1.),
+                &mut projectile_transform,
+                &GameAreaConfig::default(),
+            );
+
+            // Before and after the movement, the projectile is far from the ennemy
+            assert!(is_entities_collides(
+                (&Transform::from_xyz(0., 0., 0.), ennemy.get_moveable_interface()),
+                (&projectile_transform, projectile.get_moveable_interface())
+            ));
+            assert!(!is_entities_collides(
+                (&Transform::from_xyz(200., 0., 0.), ennemy.get_moveable_interface()),
+                (&projectile_transform, projectile.get_moveable_interface())
+            ));
+            // The impact is on the bottom of the ennemy, not at the end of the movement
+            let time_of_impact = get_collision_time(
+                (&Transform::from_xyz(0., 0., 0.), ennemy.get_moveable_interface()),
+                (&projectile_transform, projectile.get_moveable_interface()),
+            )
+            .unwrap();
+            let impact_position =
+                get_position_at_time(&projectile_transform, projectile.get_moveable_interface(), time_of_impact);
+            assert!(impact_position.y > -250. && impact_position.y < 0.);
+        });
+    }
+
+    /// Run the collision system once with the given ennemies and a single projectile of the player
+    fn hit_ennemies(
+        ennemy_positions: &[Vec2],
+        projectile: projectiles::Projectile,
+    ) -> World {
+        let mut world = World::new();
+        world.init_resource::<Events<EnnemyHit>>();
+        world.init_resource::<Events<PlayerHit>>();
+        world.init_resource::<SpatialGrid>();
+
+        for position in ennemy_positions {
+            world.spawn((
+                ennemies::Ennemy::new(&EnnemyConfig::default(), ennemies::EnnemyKind::Walker, Vec2::new(0., 1.), *position),
+                Health::new(EnnemyConfig::default().walker.health),
+                Transform::from_translation(position.extend(0.)),
+            ));
+        }
+        let projectile_transform = Transform::from_translation(projectile.get_initial_position().extend(0.));
+        world.spawn((projectile, projectile_transform));
+
+        world.run_system_once(spatial_grid::rebuild_spatial_grid_system);
+        world.run_system_once(projectile_collision_system);
+        world
+    }
+
+    /// Run the collision system once with the given ennemies, spawned in this order, and a single projectile of the player
+    /// moved during a long tick of half a second. Returns the world and the ennemies
+    fn fire_through_ennemies(
+        ennemy_positions: &[Vec2],
+        projectile: projectiles::Projectile,
+    ) -> (World, Vec<Entity>) {
+        let mut world = World::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(std::time::Duration::from_millis(500));
+        world.insert_resource(time);
+        world.init_resource::<GameConfig>();
+        world.init_resource::<Events<EnnemyHit>>();
+        world.init_resource::<Events<PlayerHit>>();
+        world.init_resource::<SpatialGrid>();
 
-        assert_eq!(is_entities_collides(ennemy.get_moveable_interface(), player.get_moveable_interface()), true);
+        let ennemies = ennemy_positions
+            .iter()
+            .map(|position| {
+                world
+                    .spawn((
+                        ennemies::Ennemy::new(&EnnemyConfig::default(), ennemies::EnnemyKind::Walker, Vec2::new(0., 1.), *position),
+                        Health::new(EnnemyConfig::default().walker.health),
+                        Transform::from_translation(position.extend(0.)),
+                    ))
+                    .id()
+            })
+            .collect();
+        let projectile_transform = Transform::from_translation(projectile.get_initial_position().extend(0.));
+        world.spawn((projectile, projectile_transform));
+
+        world.run_system_once(projectile_movement_system);
+        world.run_system_once(spatial_grid::rebuild_spatial_grid_system);
+        world.run_system_once(projectile_collision_system);
+        (world, ennemies)
     }
-    */
 
     #[test]
-    fn two_moveable_sprite_not_collides() {
-        let ennemy = ennemies::Ennemy::new(500.0, (5., 10.), (15., 20.), 50);
-        let projectile = projectiles::Projectile::new(500.0, (5., 10.), (150., 2000.), 500, false);
+    fn rocket_explodes_on_the_first_ennemy_on_its_path() {
+        // The rocket crosses both ennemies during the tick, the first one spawned is the farthest
+        let rocket = projectiles::Projectile::new(500.0, Vec2::new(0., 1.), Vec2::new(0., -250.), 900, false)
+            .with_explosion(50.);
+        let (world, ennemies) = fire_through_ennemies(&[Vec2::new(0., -30.), Vec2::new(0., -150.)], rocket);
+
+        let ennemy_hit_events = world.resource::<Events<EnnemyHit>>();
+        assert_eq!(ennemy_hit_events.len(), 1);
+        assert_eq!(ennemy_hit_events.iter_current_update_events().next().unwrap().entity, ennemies[1]);
+    }
+
+    #[test]
+    fn bullet_hits_the_nearest_ennemy_on_its_path() {
+        // The ennemy spawned last stands in front of the other one
+        let bullet = projectiles::Projectile::new(500.0, Vec2::new(0., 1.), Vec2::new(0., -250.), 700, false);
+        let (world, ennemies) = fire_through_ennemies(&[Vec2::new(0., -30.), Vec2::new(0., -150.)], bullet);
+
+        let ennemy_hit_events = world.resource::<Events<EnnemyHit>>();
+        assert_eq!(ennemy_hit_events.len(), 1);
+        assert_eq!(ennemy_hit_events.iter_current_update_events().next().unwrap().entity, ennemies[1]);
+        assert_eq!(
+            world.get::<Health>(ennemies[0]).unwrap().get_current(),
+            EnnemyConfig::default().walker.health
+        );
+    }
+
+    #[test]
+    fn piercing_projectile_hits_the_ennemies_in_the_order_of_its_path() {
+        let sniper_bullet =
+            projectiles::Projectile::new(500.0, Vec2::new(0., 1.), Vec2::new(0., -250.), 1200, false).with_pierce_count(2);
+        let (world, ennemies) = fire_through_ennemies(
+            &[Vec2::new(0., -10.), Vec2::new(0., -90.), Vec2::new(0., -170.)],
+            sniper_bullet,
+        );
+
+        let entities_hit: Vec<Entity> = world
+            .resource::<Events<EnnemyHit>>()
+            .iter_current_update_events()
+            .map(|hit| hit.entity)
+            .collect();
+        assert_eq!(entities_hit, vec![ennemies[2], ennemies[1]]);
+    }
+
+    #[test]
+    fn rocket_hits_every_ennemy_in_the_explosion() {
+        let rocket = projectiles::Projectile::new(450.0, Vec2::new(0., 1.), Vec2::ZERO, 900, false)
+            .with_explosion(120.);
+        // The third ennemy is out of the explosion
+        let mut world = hit_ennemies(&[Vec2::ZERO, Vec2::new(60., 60.), Vec2::new(400., 0.)], rocket);
+
+        assert_eq!(world.resource::<Events<EnnemyHit>>().len(), 2);
+        assert_eq!(world.query::<&projectiles::Projectile>().iter(&world).count(), 0);
+    }
+
+    #[test]
+    fn piercing_projectile_goes_through_the_ennemies() {
+        let sniper_bullet =
+            projectiles::Projectile::new(1400.0, Vec2::new(0., 1.), Vec2::ZERO, 1200, false).with_pierce_count(3);
+        let mut world = hit_ennemies(&[Vec2::ZERO, Vec2::new(5., 5.)], sniper_bullet);
+        // Still inside the ennemies at the next tick, they are not hit twice
+        world.run_system_once(projectile_collision_system);
+
+        assert_eq!(world.resource::<Events<EnnemyHit>>().len(), 2);
+        assert_eq!(world.query::<&projectiles::Projectile>().iter(&world).count(), 1);
+    }
+
+    #[test]
+    fn bullet_hits_a_single_ennemy() {
+        let bullet = projectiles::Projectile::new(700.0, Vec2::new(0., 1.), Vec2::ZERO, 700, false);
+        let mut world = hit_ennemies(&[Vec2::ZERO, Vec2::new(5., 5.)], bullet);
+        // The despawn of the bullet is applied, it does not hit again
+        world.run_system_once(projectile_collision_system);
+
+        assert_eq!(world.resource::<Events<EnnemyHit>>().len(), 1);
+        assert_eq!(world.query::<&projectiles::Projectile>().iter(&world).count(), 0);
+    }
+
+    #[test]
+    fn piercing_projectile_stops_after_its_pierce_count() {
+        let sniper_bullet =
+            projectiles::Projectile::new(1400.0, Vec2::new(0., 1.), Vec2::ZERO, 1200, false).with_pierce_count(2);
+        let mut world = hit_ennemies(&[Vec2::ZERO, Vec2::new(5., 5.), Vec2::new(-5., 5.)], sniper_bullet);
+
+        assert_eq!(world.resource::<Events<EnnemyHit>>().len(), 2);
+        assert_eq!(world.query::<&projectiles::Projectile>().iter(&world).count(), 0);
+    }
+
+    #[test]
+    fn projectile_damage_is_applied() {
+        let bullet = projectiles::Projectile::new(700.0, Vec2::new(0., 1.), Vec2::ZERO, 700, false)
+            .with_damage(EnnemyConfig::default().walker.health);
+        let mut world = hit_ennemies(&[Vec2::ZERO], bullet);
+
+        assert!(world.query::<&Health>().single(&world).is_dead());
+        let ennemy_hit_events = world.resource::<Events<EnnemyHit>>();
+        assert_eq!(ennemy_hit_events.len(), 1);
+        assert!(ennemy_hit_events
+            .iter_current_update_events()
+            .all(|hit| hit.points == EnnemyConfig::default().walker.points_per_hit));
+    }
+
+    #[test]
+    fn ennemy_contact_hurts_and_pushes_the_player_once() {
+        let game_config = GameConfig::default();
+        let mut world = World::new();
+        world.init_resource::<Events<PlayerHit>>();
+        world.init_resource::<SpatialGrid>();
+        world.spawn((
+            ennemies::Ennemy::new(&game_config.ennemy, ennemies::EnnemyKind::Walker, Vec2::new(0., 1.), Vec2::ZERO),
+            Health::new(game_config.ennemy.walker.health),
+            Transform::from_xyz(0., 0., 0.),
+        ));
+        let player_entity = world
+            .spawn((
+                player::Player::new(&game_config.player, &game_config.weapons),
+                Health::new(game_config.player.health),
+                Transform::from_xyz(0., -20., 0.),
+            ))
+            .id();
+        world.insert_resource(game_config.clone());
+
+        world.run_system_once(spatial_grid::rebuild_spatial_grid_system);
+        world.run_system_once(ennemy_contact_damage_system);
+        // Pushed down, away from the ennemy
+        assert_eq!(
+            get_position(world.get::<Transform>(player_entity).unwrap()),
+            Vec2::new(0., -20. - game_config.player.knockback_distance)
+        );
+
+        // Still invulnerable when the ennemy touches the player again
+        world.get_mut::<Transform>(player_entity).unwrap().translation = Vec3::new(0., -20., 0.);
+        world.run_system_once(ennemy_contact_damage_system);
+
+        assert_eq!(
+            world.get::<Health>(player_entity).unwrap().get_current(),
+            game_config.player.health - game_config.ennemy.walker.contact_damage
+        );
+        assert!(world.get::<player::Player>(player_entity).unwrap().is_invulnerable());
+    }
+
+    #[test]
+    fn exploder_hurts_the_player_when_it_dies() {
+        let game_config = GameConfig::default();
+        let explosion = game_config.ennemy.exploder.death_explosion.clone().unwrap();
+        let mut world = World::new();
+        world.init_resource::<Events<EnnemyKilled>>();
+        world.init_resource::<Events<PlayerHit>>();
+        let player_entity = world
+            .spawn((
+                player::Player::new(&game_config.player, &game_config.weapons),
+                Health::new(game_config.player.health),
+                Transform::from_xyz(0., 0., 0.),
+            ))
+            .id();
+        world.insert_resource(game_config.clone());
+
+        // A walker does not explode, the exploder is too far
+        world.send_event(EnnemyKilled {
+            kind: ennemies::EnnemyKind::Walker,
+            position: Vec2::ZERO,
+            points: 0,
+        });
+        world.send_event(EnnemyKilled {
+            kind: ennemies::EnnemyKind::Exploder,
+            position: Vec2::new(explosion.radius + 1., 0.),
+            points: 0,
+        });
+        world.run_system_once(ennemy_death_explosion_system);
+        assert_eq!(world.get::<Health>(player_entity).unwrap().get_current(), game_config.player.health);
+
+        world.send_event(EnnemyKilled {
+            kind: ennemies::EnnemyKind::Exploder,
+            position: Vec2::new(explosion.radius - 1., 0.),
+            points: 0,
+        });
+        world.run_system_once(ennemy_death_explosion_system);
+        assert_eq!(
+            world.get::<Health>(player_entity).unwrap().get_current(),
+            game_config.player.health - explosion.damage
+        );
+        assert!(world.get::<player::Player>(player_entity).unwrap().is_invulnerable());
+    }
+
+    #[test]
+    fn invulnerable_player_is_hit_by_a_single_projectile() {
+        let game_config = GameConfig::default();
+        let mut world = World::new();
+        world.init_resource::<Events<EnnemyHit>>();
+        world.init_resource::<Events<PlayerHit>>();
+        world.init_resource::<SpatialGrid>();
+        let player_entity = world
+            .spawn((
+                player::Player::new(&game_config.player, &game_config.weapons),
+                Health::new(game_config.player.health),
+                Transform::from_xyz(0., 0., 0.),
+            ))
+            .id();
+        // Two overlapping projectiles of the ennemies
+        for _ in 0..2 {
+            world.spawn((
+                projectiles::Projectile::new(300.0, Vec2::new(0., -1.), Vec2::ZERO, 500, true),
+                Transform::from_xyz(0., 0., 0.),
+            ));
+        }
+
+        world.run_system_once(projectile_collision_system);
 
         assert_eq!(
-            is_entities_collides(
-                ennemy.get_moveable_interface(),
-                projectile.get_moveable_interface()
-            ),
-            false
+            world.get::<Health>(player_entity).unwrap().get_current(),
+            game_config.player.health - 1
         );
+        assert_eq!(world.resource::<Events<PlayerHit>>().len(), 1);
+        // The second projectile goes through the player
+        assert_eq!(world.query::<&projectiles::Projectile>().iter(&world).count(), 1);
     }
 }
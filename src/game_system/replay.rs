@@ -0,0 +1,453 @@
+use crate::game_system::game_config::GameConfig;
+use crate::game_system::player_input::{PlayerInput, WeaponSwitch};
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+
+use std::fs;
+
+/// Header of a replay file
+static REPLAY_MAGIC: &[u8; 4] = b"SOZR";
+/// Version of the replay file format
+static REPLAY_VERSION: u8 = 1;
+/// Size of a recorded input, without its number of ticks
+static INPUT_SIZE: usize = 18;
+
+// Bit of each key in a recorded input
+static LEFT_BIT: u8 = 1;
+static RIGHT_BIT: u8 = 1 << 1;
+static UP_BIT: u8 = 1 << 2;
+static DOWN_BIT: u8 = 1 << 3;
+static FIRE_BIT: u8 = 1 << 4;
+static RELOAD_BIT: u8 = 1 << 5;
+static AIM_BIT: u8 = 1 << 6;
+
+// Recorded weapon switch: no switch, slot + 1, or one of these values
+static NO_WEAPON_SWITCH: u8 = 0;
+static NEXT_WEAPON: u8 = 0xFE;
+static PREVIOUS_WEAPON: u8 = 0xFF;
+
+/// The seed and the configuration of a game, and the input of the player at each gameplay tick.
+/// The restarts are not recorded: a replay restarts by itself after each game over.
+///
+/// File format (little endian): "SOZR", version (u8), seed (u64), size of the configuration (u32),
+/// the game configuration (RON text), then a list of runs:
+/// the keys pressed (u8), the weapon switch (u8), the aim direction (x: f32, y: f32), the analog movement (x: f32, y: f32)
+/// and the number of consecutive ticks with these inputs (u16).
+#[derive(Debug, PartialEq)]
+pub struct Replay {
+    seed: u64,
+    game_config: GameConfig,
+    inputs: Vec<PlayerInput>,
+}
+
+impl Replay {
+    /// Returns a new empty Replay
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed of the random number generator of the recorded game
+    /// * `game_config` - The configuration of the recorded game
+    ///
+    pub fn new(seed: u64, game_config: GameConfig) -> Self {
+        Replay {
+            seed,
+            game_config,
+            inputs: Vec::new(),
+        }
+    }
+
+    /// Get the seed of the recorded game
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Get the configuration of the recorded game, the replay is only played back with this configuration
+    pub fn get_game_config(&self) -> &GameConfig {
+        &self.game_config
+    }
+
+    /// Get the number of gameplay ticks recorded
+    pub fn get_number_of_ticks(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// Get the input of the player for a gameplay tick. None when the tick was not recorded
+    pub fn get_input(&self, tick: usize) -> Option<PlayerInput> {
+        self.inputs.get(tick).copied()
+    }
+
+    /// Add the input of the next gameplay tick
+    pub fn push_input(&mut self, player_input: PlayerInput) {
+        self.inputs.push(player_input);
+    }
+
+    /// Convert the replay to the bytes of a replay file
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///     # use street_of_zombies::game_system::game_config::GameConfig;
+    ///     # use street_of_zombies::game_system::replay::Replay;
+    ///     let replay = Replay::new(42, GameConfig::default());
+    ///     assert_eq!(Replay::decode(&replay.encode()), Ok(replay));
+    /// ```
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::new();
+        bytes.extend_from_slice(REPLAY_MAGIC);
+        bytes.push(REPLAY_VERSION);
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+
+        let game_config = ron::to_string(&self.game_config).expect("The game configuration cannot be written");
+        bytes.extend_from_slice(&(game_config.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(game_config.as_bytes());
+
+        let mut inputs = self.inputs.iter().map(encode_input).peekable();
+        while let Some(input) = inputs.next() {
+            let mut number_of_ticks: u16 = 1;
+            while number_of_ticks < u16::MAX && inputs.next_if_eq(&input).is_some() {
+                number_of_ticks += 1;
+            }
+
+            bytes.extend_from_slice(&input);
+            bytes.extend_from_slice(&number_of_ticks.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    /// Read a replay from the bytes of a replay file
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The content of a replay file
+    ///
+    pub fn decode(bytes: &[u8]) -> Result<Self, String> {
+        let seed_end = REPLAY_MAGIC.len() + 1 + 8;
+        let config_start = seed_end + 4;
+        if bytes.len() < config_start || &bytes[..REPLAY_MAGIC.len()] != REPLAY_MAGIC {
+            return Err("Not a replay file".to_string());
+        }
+
+        let version = bytes[REPLAY_MAGIC.len()];
+        if version != REPLAY_VERSION {
+            return Err(format!("Unsupported replay version: {}", version));
+        }
+
+        let mut seed_bytes = [0u8; 8];
+        seed_bytes.copy_from_slice(&bytes[REPLAY_MAGIC.len() + 1..seed_end]);
+
+        let mut config_size_bytes = [0u8; 4];
+        config_size_bytes.copy_from_slice(&bytes[seed_end..config_start]);
+        let header_size = config_start + u32::from_le_bytes(config_size_bytes) as usize;
+        if bytes.len() < header_size {
+            return Err("Truncated replay file".to_string());
+        }
+        let game_config = std::str::from_utf8(&bytes[config_start..header_size])
+            .map_err(|_| "Invalid game configuration in the replay".to_string())
+            .and_then(GameConfig::from_ron)?;
+
+        let mut replay = Replay::new(u64::from_le_bytes(seed_bytes), game_config);
+
+        let runs = bytes[header_size..].chunks(INPUT_SIZE + 2);
+        for run in runs {
+            if run.len() != INPUT_SIZE + 2 {
+                return Err("Truncated replay file".to_string());
+            }
+
+            let player_input = decode_input(&run[..INPUT_SIZE]);
+            let number_of_ticks = u16::from_le_bytes([run[INPUT_SIZE], run[INPUT_SIZE + 1]]);
+            for _ in 0..number_of_ticks {
+                replay.push_input(player_input);
+            }
+        }
+
+        Ok(replay)
+    }
+
+    /// Load a replay file
+    pub fn load(path: &str) -> Result<Self, String> {
+        let bytes = fs::read(path).map_err(|error| format!("Cannot read {}: {}", path, error))?;
+        Replay::decode(&bytes)
+    }
+
+    /// Save the replay in a file
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        fs::write(path, self.encode()).map_err(|error| format!("Cannot write {}: {}", path, error))
+    }
+}
+
+fn encode_input(player_input: &PlayerInput) -> Vec<u8> {
+    let mut bytes = vec![
+        input_to_bits(player_input),
+        weapon_switch_to_byte(player_input.weapon_switch),
+    ];
+
+    // The exact bits are recorded, the replayed game aims exactly like the recorded one
+    let aim = player_input.aim.unwrap_or(Vec2::ZERO);
+    bytes.extend_from_slice(&aim.x.to_le_bytes());
+    bytes.extend_from_slice(&aim.y.to_le_bytes());
+    // No analog movement is recorded as (0, 0), the stick is never exactly in its center outside of the deadzone
+    let movement = player_input.movement.unwrap_or(Vec2::ZERO);
+    bytes.extend_from_slice(&movement.x.to_le_bytes());
+    bytes.extend_from_slice(&movement.y.to_le_bytes());
+
+    bytes
+}
+
+fn decode_input(bytes: &[u8]) -> PlayerInput {
+    let mut player_input = input_from_bits(bytes[0]);
+    player_input.weapon_switch = weapon_switch_from_byte(bytes[1]);
+
+    if bytes[0] & AIM_BIT != 0 {
+        let aim_x = f32::from_le_bytes([bytes[2], bytes[3], bytes[4], bytes[5]]);
+        let aim_y = f32::from_le_bytes([bytes[6], bytes[7], bytes[8], bytes[9]]);
+        player_input.aim = Some(Vec2::new(aim_x, aim_y));
+    }
+
+    let movement_x = f32::from_le_bytes([bytes[10], bytes[11], bytes[12], bytes[13]]);
+    let movement_y = f32::from_le_bytes([bytes[14], bytes[15], bytes[16], bytes[17]]);
+    let movement = Vec2::new(movement_x, movement_y);
+    if movement != Vec2::ZERO {
+        player_input.movement = Some(movement);
+    }
+
+    player_input
+}
+
+fn input_to_bits(player_input: &PlayerInput) -> u8 {
+    let mut keys: u8 = 0;
+
+    for (pressed, bit) in [
+        (player_input.left, LEFT_BIT),
+        (player_input.right, RIGHT_BIT),
+        (player_input.up, UP_BIT),
+        (player_input.down, DOWN_BIT),
+        (player_input.fire, FIRE_BIT),
+        (player_input.reload, RELOAD_BIT),
+        (player_input.aim.is_some(), AIM_BIT),
+    ] {
+        if pressed {
+            keys |= bit;
+        }
+    }
+
+    keys
+}
+
+fn input_from_bits(keys: u8) -> PlayerInput {
+    PlayerInput {
+        left: keys & LEFT_BIT != 0,
+        right: keys & RIGHT_BIT != 0,
+        up: keys & UP_BIT != 0,
+        down: keys & DOWN_BIT != 0,
+        fire: keys & FIRE_BIT != 0,
+        restart: false,
+        reload: keys & RELOAD_BIT != 0,
+        weapon_switch: None,
+        aim: None,
+        movement: None,
+    }
+}
+
+fn weapon_switch_to_byte(weapon_switch: Option<WeaponSwitch>) -> u8 {
+    match weapon_switch {
+        None => NO_WEAPON_SWITCH,
+        Some(WeaponSwitch::Slot(slot)) => slot.saturating_add(1).min(NEXT_WEAPON - 1),
+        Some(WeaponSwitch::Next) => NEXT_WEAPON,
+        Some(WeaponSwitch::Previous) => PREVIOUS_WEAPON,
+    }
+}
+
+fn weapon_switch_from_byte(weapon_switch: u8) -> Option<WeaponSwitch> {
+    match weapon_switch {
+        byte if byte == NO_WEAPON_SWITCH => None,
+        byte if byte == NEXT_WEAPON => Some(WeaponSwitch::Next),
+        byte if byte == PREVIOUS_WEAPON => Some(WeaponSwitch::Previous),
+        slot => Some(WeaponSwitch::Slot(slot - 1)),
+    }
+}
+
+/// Records the input of each gameplay tick, the replay is saved when the game exits
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    path: String,
+    replay: Replay,
+}
+
+impl ReplayRecorder {
+    /// Returns a new ReplayRecorder
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the replay file to write
+    /// * `seed` - The seed of the random number generator of the recorded game
+    /// * `game_config` - The configuration of the recorded game
+    ///
+    pub fn new(path: String, seed: u64, game_config: GameConfig) -> Self {
+        ReplayRecorder {
+            path,
+            replay: Replay::new(seed, game_config),
+        }
+    }
+
+    /// Save the recorded replay
+    pub fn save(&self) -> Result<(), String> {
+        self.replay.save(&self.path)
+    }
+}
+
+/// Plays back a replay instead of the keyboard
+#[derive(Resource)]
+pub struct ReplayPlayer {
+    replay: Replay,
+    next_tick: usize,
+}
+
+impl ReplayPlayer {
+    /// Returns a new ReplayPlayer, starting at the first tick of the replay
+    pub fn new(replay: Replay) -> Self {
+        ReplayPlayer {
+            replay,
+            next_tick: 0,
+        }
+    }
+
+    /// True when every recorded tick was played
+    pub fn is_finished(&self) -> bool {
+        self.next_tick >= self.replay.get_number_of_ticks()
+    }
+}
+
+/// Game System: Feed the input recorded for this gameplay tick. Managed by as a "Bevy System"
+pub fn replay_input_system(
+    mut replay_player: ResMut<ReplayPlayer>,
+    mut player_input: ResMut<PlayerInput>,
+) {
+    // Once the replay is finished, the player does not touch anything anymore
+    *player_input = replay_player
+        .replay
+        .get_input(replay_player.next_tick)
+        .unwrap_or_default();
+    replay_player.next_tick += 1;
+}
+
+/// Game System: Record the input used by this gameplay tick. Managed by as a "Bevy System"
+pub fn record_input_system(
+    mut replay_recorder: ResMut<ReplayRecorder>,
+    player_input: Res<PlayerInput>,
+) {
+    replay_recorder.replay.push_input(*player_input);
+}
+
+/// Save the recorded replay when the application exits. Managed by as a "Bevy System"
+pub fn save_replay_on_exit_system(
+    mut exit_events: EventReader<AppExit>,
+    replay_recorder: Res<ReplayRecorder>,
+) {
+    if exit_events.read().next().is_some() {
+        if let Err(error) = replay_recorder.save() {
+            error!("{}", error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_replay() {
+        let mut replay = Replay::new(1234, GameConfig::default());
+        replay.push_input(PlayerInput::default());
+        replay.push_input(PlayerInput {
+            left: true,
+            fire: true,
+            ..Default::default()
+        });
+        replay.push_input(PlayerInput {
+            reload: true,
+            ..Default::default()
+        });
+        replay.push_input(PlayerInput {
+            fire: true,
+            aim: Some(Vec2::new(0.6, -0.8)),
+            ..Default::default()
+        });
+        replay.push_input(PlayerInput {
+            movement: Some(Vec2::new(-0.25, 0.5)),
+            ..Default::default()
+        });
+        for weapon_switch in [WeaponSwitch::Slot(2), WeaponSwitch::Next, WeaponSwitch::Previous] {
+            replay.push_input(PlayerInput {
+                weapon_switch: Some(weapon_switch),
+                ..Default::default()
+            });
+        }
+
+        assert_eq!(Replay::decode(&replay.encode()), Ok(replay));
+    }
+
+    #[test]
+    fn replay_keeps_its_game_config() {
+        let mut game_config = GameConfig::default();
+        game_config.player.speed = 123.5;
+        game_config.game_area.limit_x = 640.;
+        let replay = Replay::new(1, game_config.clone());
+
+        let decoded_replay = Replay::decode(&replay.encode()).unwrap();
+        assert_eq!(decoded_replay.get_game_config(), &game_config);
+    }
+
+    #[test]
+    fn replay_with_invalid_game_config_is_refused() {
+        let mut game_config = GameConfig::default();
+        game_config.game_area.limit_x = -1.;
+
+        assert!(Replay::decode(&Replay::new(1, game_config).encode()).is_err());
+    }
+
+    #[test]
+    fn identical_ticks_are_compressed() {
+        let mut replay = Replay::new(1, GameConfig::default());
+        for _ in 0..1000 {
+            replay.push_input(PlayerInput {
+                up: true,
+                ..Default::default()
+            });
+        }
+
+        // Header + configuration + a single run
+        let game_config_size = ron::to_string(&GameConfig::default()).unwrap().len();
+        assert_eq!(replay.encode().len(), REPLAY_MAGIC.len() + 1 + 8 + 4 + game_config_size + INPUT_SIZE + 2);
+        assert_eq!(Replay::decode(&replay.encode()), Ok(replay));
+    }
+
+    #[test]
+    fn long_runs_are_split() {
+        let mut replay = Replay::new(1, GameConfig::default());
+        for _ in 0..(u16::MAX as u32 + 10) {
+            replay.push_input(PlayerInput::default());
+        }
+
+        assert_eq!(Replay::decode(&replay.encode()), Ok(replay));
+    }
+
+    #[test]
+    fn decode_invalid_file() {
+        assert!(Replay::decode(b"NOT A REPLAY").is_err());
+        assert!(Replay::decode(b"SOZR").is_err());
+
+        let mut bytes = Replay::new(1, GameConfig::default()).encode();
+        bytes[REPLAY_MAGIC.len()] = REPLAY_VERSION + 1;
+        assert!(Replay::decode(&bytes).is_err());
+    }
+
+    #[test]
+    fn decode_truncated_file() {
+        let mut bytes = Replay::new(1, GameConfig::default()).encode();
+        bytes.push(FIRE_BIT);
+
+        assert!(Replay::decode(&bytes).is_err());
+    }
+}
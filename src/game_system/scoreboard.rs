@@ -1,96 +1,157 @@
-use instant::*;
-
 use bevy::prelude::*;
-use bevy::ecs::component::Component;
 
+use std::time::Duration;
+
+use crate::game_system::game_config::{DifficultyConfig, GameConfig};
+use crate::game_system::gameplay_events::{DifficultyIncreased, EnnemyHit, EnnemyKilled, PlayerDied, ShotFired};
+use crate::game_system::input_bindings::{InputAction, InputBindings};
 use crate::{GAME_RESOLUTION_HEIGHT, GAME_RESOLUTION_WIDTH};
 
-static INITIAL_PLAYER_HEALTH: u32 = 5;
 static MAX_DIFFICULTY_LEVEL: u32 = 5;
-static SECONDS_ELAPSED_BEFORE_NEXT_DIFFICULTY: u32 = 30;
 
+#[derive(Default)]
 struct ScoreAndInfoInternal {
     score: u32,
-    health: u32,
+    /// True once the player is dead
+    is_gameover: bool,
     percent_until_next_level: u32,
     difficulty_level: u32,
-    start_time: Instant,
+    /// Game time spent in the current difficulty level
+    time_in_difficulty_level: Duration,
+    /// Shots fired by the player
+    shots_fired: u32,
+    /// Hits of the player on the ennemies
+    ennemies_hit: u32,
+    ennemies_killed: u32,
 }
 
-#[derive(Component)]
+/// Game System: Advance the difficulty timer by the duration of the gameplay tick, then send `DifficultyIncreased`
+/// when the next level is reached. Managed by as a "Bevy System"
+pub fn difficulty_timer_system(
+    time: Res<Time>,
+    game_config: Res<GameConfig>,
+    mut score: ResMut<ScoreAndInfo>,
+    mut difficulty_events: EventWriter<DifficultyIncreased>,
+) {
+    let previous_difficulty_level = score.get_difficulty_level();
+    score.update_percent_until_next_level(time.delta(), &game_config.difficulty);
+
+    if score.get_difficulty_level() > previous_difficulty_level {
+        difficulty_events.send(DifficultyIncreased {
+            difficulty_level: score.get_difficulty_level(),
+        });
+    }
+}
+
+/// Game System: Listen to the gameplay events to update the score, the statistics of the player and the game over.
+/// Managed by as a "Bevy System"
+pub fn score_system(
+    mut ennemy_hit_events: EventReader<EnnemyHit>,
+    mut ennemy_killed_events: EventReader<EnnemyKilled>,
+    mut player_died_events: EventReader<PlayerDied>,
+    mut shot_fired_events: EventReader<ShotFired>,
+    mut score: ResMut<ScoreAndInfo>,
+) {
+    for ennemy_hit in ennemy_hit_events.read() {
+        score.add_to_score(ennemy_hit.points);
+        score.score_data.ennemies_hit += 1;
+    }
+    for ennemy_killed in ennemy_killed_events.read() {
+        score.add_to_score(ennemy_killed.points);
+        score.score_data.ennemies_killed += 1;
+    }
+    score.score_data.shots_fired += shot_fired_events.read().filter(|shot| !shot.is_from_ennemy).count() as u32;
+
+    if player_died_events.read().next().is_some() {
+        score.set_gameover();
+    }
+}
+
+/// The score, the game over and the difficulty of the current game. The health of the player is its `Health` component
+#[derive(Resource, Default)]
 pub struct ScoreAndInfo {
     score_data: ScoreAndInfoInternal,
 }
 
 impl ScoreAndInfo {
+    /// Returns a new ScoreAndInfo, at the beginning of a game
     pub fn new() -> Self {
-        ScoreAndInfo {
-            score_data: ScoreAndInfoInternal {
-                score: 0,
-                health: INITIAL_PLAYER_HEALTH,
-                difficulty_level: 0,
-                percent_until_next_level: 0,
-                start_time: Instant::now(),
-            },
-        }
+        ScoreAndInfo::default()
     }
 
     pub fn add_to_score(&mut self, score_added: u32) {
         self.score_data.score += score_added;
     }
 
-    pub fn remove_health(&mut self, health_to_remove: u32) {
-        let remove_health_result = self.score_data.health.overflowing_sub(health_to_remove);
-        match remove_health_result {
-            (new_health, false) => self.score_data.health = new_health,
-            (_, true) => self.score_data.health = 0,
-        }
+    /// The player is dead, the game is over
+    pub fn set_gameover(&mut self) {
+        self.score_data.is_gameover = true;
     }
 
-    pub fn update_percent_until_next_level(&mut self) {
+    /// Advance the difficulty timer with the game time. The difficulty level increases every
+    /// `seconds_before_next_level` seconds of game (the pauses are not counted).
+    /// The time elapsed beyond a level change counts for the next level.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_elapsed_since_last_update` - The game time elapsed since the last update
+    /// * `difficulty` - The configuration of the difficulty progression
+    ///
+    pub fn update_percent_until_next_level(
+        &mut self,
+        time_elapsed_since_last_update: Duration,
+        difficulty: &DifficultyConfig,
+    ) {
         if self.score_data.difficulty_level == MAX_DIFFICULTY_LEVEL {
             return;
         }
 
-        let second_for_next_difficulty_level = SECONDS_ELAPSED_BEFORE_NEXT_DIFFICULTY as u64;
-        let mut percent_elapsed = ((self.score_data.start_time.elapsed().as_secs() * 100)
-            / second_for_next_difficulty_level) as u32;
+        let time_for_next_difficulty_level =
+            Duration::from_secs(difficulty.seconds_before_next_level.max(1) as u64);
+        self.score_data.time_in_difficulty_level += time_elapsed_since_last_update;
 
-        if percent_elapsed >= 100 {
-            self.score_data.start_time = Instant::now();
+        if self.score_data.time_in_difficulty_level >= time_for_next_difficulty_level {
+            self.score_data.time_in_difficulty_level -= time_for_next_difficulty_level;
             self.increase_difficulty_level();
-            percent_elapsed = 0;
         }
 
-        self.score_data.percent_until_next_level = percent_elapsed;
+        self.score_data.percent_until_next_level = ((self.score_data.time_in_difficulty_level.as_secs()
+            * 100)
+            / time_for_next_difficulty_level.as_secs()) as u32;
     }
 
-    pub fn update_scoarboard_text(&self, text: &mut Text, style: &mut Style) {
-        let difficulty_level_list = vec![
-            "EASY",
-            "NORMAL",
-            "HARD",
-            "EXTREME",
-            "STILL OK?",
-            "!YOU'LL DIE!",
-        ];
-
-        let difficulty_text =
-            match difficulty_level_list.get(self.score_data.difficulty_level as usize) {
-                Some(difficulty_level) => difficulty_level,
-                None => "UNKNOWN",
-            };
+    /// Update the scoreboard text. The seed of the game and the restart keys are printed on the "Game Over" screen.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text of the scoreboard
+    /// * `style` - The style of the scoreboard
+    /// * `player_health` - The current and maximum health of the player
+    /// * `seed` - The seed of the random number generator of the game
+    /// * `input_bindings` - The keys of the player
+    ///
+    pub fn update_scoarboard_text(
+        &self,
+        text: &mut Text,
+        style: &mut Style,
+        player_health: (u32, u32),
+        seed: u64,
+        input_bindings: &InputBindings,
+    ) {
+        let difficulty_text = get_difficulty_name(self.score_data.difficulty_level);
 
         if self.is_gameover() {
-            self.print_board_game_over(text, style);
+            self.print_board_game_over(text, style, seed, input_bindings);
         } else {
-            self.print_board_continue(text, difficulty_text);
+            self.print_board_continue(text, style, player_health, difficulty_text);
         }
     }
 
-    fn print_board_continue(&self, text: &mut Text, difficulty_text: &str) {
+    fn print_board_continue(&self, text: &mut Text, style: &mut Style, player_health: (u32, u32), difficulty_text: &str) {
+        style.top = Val::Px(0.0);
+        style.left = Val::Px(0.0);
         text.sections[0].value = format!("SCORE: {:10}", self.get_score());
-        text.sections[1].value = format!(" - HEALTH: {:2}", self.get_health());
+        text.sections[1].value = format!(" - HEALTH: {:2}/{}", player_health.0, player_health.1);
         text.sections[2].value = format!(
             " -  DIFFICULTY : {:20} - {:3}%",
             difficulty_text,
@@ -98,16 +159,12 @@ impl ScoreAndInfo {
         );
     }
 
-    fn get_score(&self) -> u32 {
+    pub fn get_score(&self) -> u32 {
         self.score_data.score
     }
 
-    fn get_health(&self) -> u32 {
-        self.score_data.health
-    }
-
     pub fn is_gameover(&self) -> bool {
-        self.score_data.health == 0
+        self.score_data.is_gameover
     }
 
     pub fn get_difficulty_level(&self) -> u32 {
@@ -130,12 +187,46 @@ impl ScoreAndInfo {
         }
     }
 
-    fn print_board_game_over(&self, text: &mut Text, style: &mut Style) {
+    fn print_board_game_over(&self, text: &mut Text, style: &mut Style, seed: u64, input_bindings: &InputBindings) {
         style.top = Val::Px(GAME_RESOLUTION_HEIGHT / 4.);
         style.left = Val::Px(GAME_RESOLUTION_WIDTH / 4.);
-        text.sections[0].value = format!("- GAME OVER -    ");
-        text.sections[1].value = format!("Score =  {:10}\n", self.get_score());
-        text.sections[2].value = format!(" - PRESS R TO RESTART -");
+        text.sections[0].value = "- GAME OVER -    ".to_string();
+        text.sections[1].value = format!(
+            "Score =  {:10}\nShots =  {} - Hits =  {} - Kills =  {}\nSeed =  {}\n",
+            self.get_score(),
+            self.score_data.shots_fired,
+            self.score_data.ennemies_hit,
+            self.score_data.ennemies_killed,
+            seed
+        );
+        text.sections[2].value = format!(
+            " - PRESS {} TO RESTART -",
+            input_bindings.get_keys_name(InputAction::Restart).to_uppercase()
+        );
+    }
+}
+
+/// Get the name of a difficulty level, as printed on the scoreboard
+///
+/// # Examples
+///
+/// ```
+///     # use street_of_zombies::game_system::scoreboard::get_difficulty_name;
+///     assert_eq!(get_difficulty_name(0), "EASY");
+/// ```
+pub fn get_difficulty_name(difficulty_level: u32) -> &'static str {
+    let difficulty_level_list = [
+        "EASY",
+        "NORMAL",
+        "HARD",
+        "EXTREME",
+        "STILL OK?",
+        "!YOU'LL DIE!",
+    ];
+
+    match difficulty_level_list.get(difficulty_level as usize) {
+        Some(difficulty_level) => difficulty_level,
+        None => "UNKNOWN",
     }
 }
 
@@ -143,8 +234,6 @@ impl ScoreAndInfo {
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
-    use std::thread;
-    use std::time::Duration;
 
     #[test]
     fn score_board_add_system_test() {
@@ -154,21 +243,6 @@ mod tests {
         assert_eq!(player_data.get_score(), 5000);
     }
 
-    #[test]
-    fn health_overflow_test() {
-        let mut player_data = ScoreAndInfo::new();
-        player_data.remove_health(5000);
-
-        assert_eq!(player_data.get_health(), 0);
-    }
-    #[test]
-    fn health_remove_test() {
-        let mut player_data = ScoreAndInfo::new();
-        player_data.remove_health(1);
-
-        assert_eq!(player_data.get_health(), INITIAL_PLAYER_HEALTH - 1);
-    }
-
     #[test]
     fn increase_difficulty_level_test() {
         let mut player_data = ScoreAndInfo::new();
@@ -190,34 +264,96 @@ mod tests {
 
     #[test]
     fn percent_test() {
-        // This test can be problematic (Usage of sleep of 1 second). It is header to test if the "Percent" system works
         let mut player_data = ScoreAndInfo::new();
 
-        let one_second = Duration::from_secs(1);
-        thread::sleep(one_second);
-        player_data.update_percent_until_next_level();
+        player_data.update_percent_until_next_level(Duration::from_secs(1), &DifficultyConfig::default());
 
-        let percent_for_one_sec = 100 / SECONDS_ELAPSED_BEFORE_NEXT_DIFFICULTY;
+        let percent_for_one_sec = 100 / DifficultyConfig::default().seconds_before_next_level;
 
-        assert_eq!(
-            player_data.get_percent_until_next_difficulty_level() >= percent_for_one_sec,
-            true
-        );
+        assert_eq!(player_data.get_percent_until_next_difficulty_level(), percent_for_one_sec);
+    }
+
+    #[test]
+    fn next_difficulty_level_after_enough_game_time_test() {
+        let mut player_data = ScoreAndInfo::new();
+        let difficulty = DifficultyConfig::default();
+        let update_duration = Duration::from_millis(100);
+
+        for _ in 0..difficulty.seconds_before_next_level * 10 - 1 {
+            player_data.update_percent_until_next_level(update_duration, &difficulty);
+        }
+        assert_eq!(player_data.get_difficulty_level(), 0);
+
+        player_data.update_percent_until_next_level(update_duration, &difficulty);
+        assert_eq!(player_data.get_difficulty_level(), 1);
+        assert_eq!(player_data.get_percent_until_next_difficulty_level(), 0);
+    }
+
+    #[test]
+    fn time_beyond_a_level_change_counts_for_the_next_level() {
+        let mut player_data = ScoreAndInfo::new();
+        let difficulty = DifficultyConfig::default();
+        let time_for_next_level = Duration::from_secs(difficulty.seconds_before_next_level as u64);
+
+        player_data.update_percent_until_next_level(time_for_next_level - Duration::from_millis(100), &difficulty);
+        // A tick going 200 ms over the level change
+        player_data.update_percent_until_next_level(Duration::from_millis(300), &difficulty);
+        assert_eq!(player_data.get_difficulty_level(), 1);
+
+        player_data.update_percent_until_next_level(time_for_next_level - Duration::from_millis(200), &difficulty);
+        assert_eq!(player_data.get_difficulty_level(), 2);
+        assert_eq!(player_data.get_percent_until_next_difficulty_level(), 0);
     }
 
     #[test]
     fn game_over_test() {
         let mut player_data = ScoreAndInfo::new();
 
-        player_data.remove_health(INITIAL_PLAYER_HEALTH);
+        player_data.set_gameover();
 
-        assert_eq!(player_data.is_gameover(), true);
+        assert!(player_data.is_gameover());
+    }
+
+    #[test]
+    fn score_system_listens_to_the_gameplay_events() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut world = World::new();
+        world.init_resource::<ScoreAndInfo>();
+        world.init_resource::<Events<EnnemyHit>>();
+        world.init_resource::<Events<EnnemyKilled>>();
+        world.init_resource::<Events<PlayerDied>>();
+        world.init_resource::<Events<ShotFired>>();
+
+        world.send_event(ShotFired { is_from_ennemy: false });
+        world.send_event(ShotFired { is_from_ennemy: true });
+        world.send_event(EnnemyHit {
+            entity: Entity::PLACEHOLDER,
+            points: 10,
+        });
+        world.send_event(EnnemyKilled {
+            kind: crate::game_entity::ennemies::EnnemyKind::Walker,
+            position: Vec2::ZERO,
+            points: 50,
+        });
+        world.run_system_once(score_system);
+
+        let score = world.resource::<ScoreAndInfo>();
+        assert_eq!(score.get_score(), 60);
+        assert_eq!(score.score_data.shots_fired, 1);
+        assert_eq!(score.score_data.ennemies_hit, 1);
+        assert_eq!(score.score_data.ennemies_killed, 1);
+        assert!(!score.is_gameover());
+
+        world.send_event(PlayerDied);
+        world.run_system_once(score_system);
+        assert!(world.resource::<ScoreAndInfo>().is_gameover());
     }
 
     #[test]
     fn game_continue_test() {
         let player_data = ScoreAndInfo::new();
 
-        assert_eq!(player_data.is_gameover(), false);
+        assert!(!player_data.is_gameover());
     }
 }
@@ -0,0 +1,156 @@
+use crate::game_entity::*;
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+/// Size of a cell of the collision grid, about the size of a zombie
+static GRID_CELL_SIZE: f32 = 64.;
+
+/// A uniform grid over the game area, each cell lists the ennemies whose hitbox overlaps it.
+/// A projectile is only checked against the ennemies of the cells it overlaps, instead of every ennemy.
+///
+/// Rebuilt at each gameplay tick, before the collisions, from the position and the hitbox of the ennemies.
+#[derive(Resource)]
+pub struct SpatialGrid {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<Entity>>,
+}
+
+impl Default for SpatialGrid {
+    fn default() -> Self {
+        SpatialGrid::new(GRID_CELL_SIZE)
+    }
+}
+
+impl SpatialGrid {
+    /// Returns a new empty SpatialGrid
+    ///
+    /// # Arguments
+    ///
+    /// * `cell_size` - The width and the height of a cell
+    ///
+    pub fn new(cell_size: f32) -> Self {
+        SpatialGrid {
+            cell_size,
+            cells: HashMap::default(),
+        }
+    }
+
+    /// Remove every entity from the grid. The cells keep their memory for the next tick
+    pub fn clear(&mut self) {
+        for entities in self.cells.values_mut() {
+            entities.clear();
+        }
+    }
+
+    /// Add an entity in every cell overlapped by its hitbox
+    ///
+    /// # Arguments
+    ///
+    /// * `entity` - The entity to add
+    /// * `position` - The position of the entity, center of its hitbox
+    /// * `hitbox_size` - The size of the hitbox of the entity
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///     # use bevy::prelude::*;
+    ///     # use street_of_zombies::game_system::spatial_grid::SpatialGrid;
+    ///     # let mut spatial_grid = SpatialGrid::default();
+    ///     # let ennemy_entity = Entity::from_raw(0);
+    ///     spatial_grid.insert(ennemy_entity, Vec2::new(15., 20.), Vec2::new(40., 50.));
+    /// ```
+    pub fn insert(&mut self, entity: Entity, position: Vec2, hitbox_size: Vec2) {
+        let (min_cell, max_cell) = self.get_cell_range(position, hitbox_size);
+
+        for cell_x in min_cell.0..=max_cell.0 {
+            for cell_y in min_cell.1..=max_cell.1 {
+                self.cells.entry((cell_x, cell_y)).or_default().push(entity);
+            }
+        }
+    }
+
+    /// Get the entities which may collide with a hitbox: the entities sharing a cell with it.
+    /// Each entity is returned once, sorted, the collisions are always treated in the same order
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The center of the hitbox
+    /// * `hitbox_size` - The size of the hitbox
+    ///
+    pub fn get_candidates(&self, position: Vec2, hitbox_size: Vec2) -> Vec<Entity> {
+        let (min_cell, max_cell) = self.get_cell_range(position, hitbox_size);
+        let mut candidates = Vec::new();
+
+        for cell_x in min_cell.0..=max_cell.0 {
+            for cell_y in min_cell.1..=max_cell.1 {
+                if let Some(entities) = self.cells.get(&(cell_x, cell_y)) {
+                    candidates.extend_from_slice(entities);
+                }
+            }
+        }
+
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates
+    }
+
+    /// Get the first and the last cells (x, y) overlapped by a hitbox
+    fn get_cell_range(&self, position: Vec2, hitbox_size: Vec2) -> ((i32, i32), (i32, i32)) {
+        let min_corner = ((position - hitbox_size / 2.) / self.cell_size).floor();
+        let max_corner = ((position + hitbox_size / 2.) / self.cell_size).floor();
+
+        (
+            (min_corner.x as i32, min_corner.y as i32),
+            (max_corner.x as i32, max_corner.y as i32),
+        )
+    }
+}
+
+/// Game System: Fill the collision grid with the ennemies at their current position. Managed by as a "Bevy System"
+pub fn rebuild_spatial_grid_system(
+    mut spatial_grid: ResMut<SpatialGrid>,
+    ennemy_query: Query<(Entity, &ennemies::Ennemy, &Transform)>,
+) {
+    spatial_grid.clear();
+
+    for (entity, ennemy, transform) in ennemy_query.iter() {
+        spatial_grid.insert(
+            entity,
+            get_position(transform),
+            ennemy.get_moveable_interface().get_hitbox_size(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_close_entities_are_candidates() {
+        let mut spatial_grid = SpatialGrid::new(64.);
+        let close_entity = Entity::from_raw(1);
+        let far_entity = Entity::from_raw(2);
+        spatial_grid.insert(close_entity, Vec2::new(10., 10.), Vec2::new(40., 50.));
+        spatial_grid.insert(far_entity, Vec2::new(400., -300.), Vec2::new(40., 50.));
+
+        assert_eq!(
+            spatial_grid.get_candidates(Vec2::new(0., 0.), Vec2::new(10., 10.)),
+            vec![close_entity]
+        );
+    }
+
+    #[test]
+    fn entity_over_several_cells_is_returned_once() {
+        let mut spatial_grid = SpatialGrid::new(64.);
+        let entity = Entity::from_raw(1);
+        // On the corner of four cells
+        spatial_grid.insert(entity, Vec2::ZERO, Vec2::new(40., 50.));
+
+        assert_eq!(spatial_grid.get_candidates(Vec2::ZERO, Vec2::new(200., 200.)), vec![entity]);
+
+        spatial_grid.clear();
+        assert!(spatial_grid.get_candidates(Vec2::ZERO, Vec2::new(200., 200.)).is_empty());
+    }
+}
@@ -0,0 +1,146 @@
+/// Number of ticks simulated by the headless mode when no value is given
+static DEFAULT_HEADLESS_TICKS: u32 = 36_000;
+
+/// The options given on the command line when launching the game
+#[derive(Debug, PartialEq)]
+pub struct LaunchOptions {
+    /// Some(number_of_ticks) when the game runs without window nor GPU
+    pub headless_ticks: Option<u32>,
+    /// The seed of the random number generator. A random seed is used when missing
+    pub seed: Option<u64>,
+    /// The replay file where the inputs of the player are recorded
+    pub record_path: Option<String>,
+    /// The replay file played instead of the keyboard
+    pub replay_path: Option<String>,
+}
+
+impl LaunchOptions {
+    /// Parse the command line arguments (without the program name)
+    ///
+    /// # Arguments
+    ///
+    /// * `arguments` - The arguments given to the program
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///     let launch_options = LaunchOptions::from_arguments(std::env::args().skip(1))?;
+    /// ```
+    pub fn from_arguments<I>(arguments: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut launch_options = LaunchOptions {
+            headless_ticks: None,
+            seed: None,
+            record_path: None,
+            replay_path: None,
+        };
+        let mut arguments = arguments.into_iter().peekable();
+
+        while let Some(argument) = arguments.next() {
+            match argument.as_str() {
+                "--headless" => {
+                    let ticks = match arguments.next_if(|value| !value.starts_with("--")) {
+                        Some(value) => value
+                            .parse::<u32>()
+                            .map_err(|_| format!("Invalid number of ticks: {}", value))?,
+                        None => DEFAULT_HEADLESS_TICKS,
+                    };
+                    launch_options.headless_ticks = Some(ticks);
+                }
+                "--seed" => {
+                    let value = arguments
+                        .next()
+                        .ok_or_else(|| "Missing value for --seed".to_string())?;
+                    let seed = value
+                        .parse::<u64>()
+                        .map_err(|_| format!("Invalid seed: {}", value))?;
+                    launch_options.seed = Some(seed);
+                }
+                "--record" => {
+                    let path = arguments
+                        .next()
+                        .ok_or_else(|| "Missing file for --record".to_string())?;
+                    launch_options.record_path = Some(path);
+                }
+                "--replay" => {
+                    let path = arguments
+                        .next()
+                        .ok_or_else(|| "Missing file for --replay".to_string())?;
+                    launch_options.replay_path = Some(path);
+                }
+                unknown => return Err(format!("Unknown argument: {}", unknown)),
+            }
+        }
+
+        if launch_options.record_path.is_some() && launch_options.replay_path.is_some() {
+            return Err("--record and --replay cannot be used together".to_string());
+        }
+
+        Ok(launch_options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(arguments: &[&str]) -> Result<LaunchOptions, String> {
+        LaunchOptions::from_arguments(arguments.iter().map(|argument| argument.to_string()))
+    }
+
+    #[test]
+    fn no_argument_is_windowed() {
+        assert_eq!(parse(&[]).unwrap().headless_ticks, None);
+    }
+
+    #[test]
+    fn headless_with_ticks() {
+        assert_eq!(parse(&["--headless", "500"]).unwrap().headless_ticks, Some(500));
+    }
+
+    #[test]
+    fn headless_default_ticks() {
+        assert_eq!(
+            parse(&["--headless"]).unwrap().headless_ticks,
+            Some(DEFAULT_HEADLESS_TICKS)
+        );
+    }
+
+    #[test]
+    fn headless_invalid_ticks() {
+        assert!(parse(&["--headless", "many"]).is_err());
+    }
+
+    #[test]
+    fn seed_with_headless() {
+        let launch_options = parse(&["--headless", "--seed", "42"]).unwrap();
+        assert_eq!(launch_options.headless_ticks, Some(DEFAULT_HEADLESS_TICKS));
+        assert_eq!(launch_options.seed, Some(42));
+    }
+
+    #[test]
+    fn seed_missing_value() {
+        assert!(parse(&["--seed"]).is_err());
+    }
+
+    #[test]
+    fn record_and_replay_paths() {
+        let launch_options = parse(&["--record", "game.sozr"]).unwrap();
+        assert_eq!(launch_options.record_path, Some("game.sozr".to_string()));
+
+        let launch_options = parse(&["--headless", "--replay", "game.sozr"]).unwrap();
+        assert_eq!(launch_options.replay_path, Some("game.sozr".to_string()));
+    }
+
+    #[test]
+    fn record_and_replay_together() {
+        assert!(parse(&["--record", "a.sozr", "--replay", "b.sozr"]).is_err());
+    }
+
+    #[test]
+    fn unknown_argument() {
+        assert!(parse(&["--fullscreen"]).is_err());
+    }
+}
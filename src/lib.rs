@@ -0,0 +1,9 @@
+pub mod game_entity;
+pub mod game_system;
+pub mod presentation_system;
+pub mod sprite_manager_system;
+pub mod weapons;
+
+// Resolution
+pub static GAME_RESOLUTION_WIDTH: f32 = 1024.0;
+pub static GAME_RESOLUTION_HEIGHT: f32 = 720.0;
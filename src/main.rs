@@ -1,22 +1,94 @@
-mod game_entity;
-mod game_system;
-mod sprite_manager_system;
-mod weapons;
+mod launch_options;
 
 use bevy::prelude::*;
+use bevy::input::InputPlugin;
 use bevy::window::WindowPlugin;
 use bevy::render::settings::{WgpuSettings, Backends, RenderCreation};
 use bevy::render::RenderPlugin;
+use bevy::time::TimeUpdateStrategy;
 
-use crate::game_system::*;
+use std::time::Duration;
 
-// Resolution
-static GAME_RESOLUTION_WIDTH: f32 = 1024.0;
-static GAME_RESOLUTION_HEIGHT: f32 = 720.0;
+use street_of_zombies::game_system::*;
+use street_of_zombies::game_system::game_config::{GameConfig, GameConfigWatcher, GAME_CONFIG_PATH};
+use street_of_zombies::game_system::input_bindings::{InputBindings, SettingsFile, SETTINGS_PATH};
+use street_of_zombies::game_system::game_state::GameState;
+use street_of_zombies::game_system::math_and_generator::GameRng;
+use street_of_zombies::game_system::replay::{Replay, ReplayPlayer, ReplayRecorder};
+use street_of_zombies::game_system::scoreboard::ScoreAndInfo;
+use street_of_zombies::presentation_system::StreetOfZombiesPresentation;
+use street_of_zombies::{GAME_RESOLUTION_HEIGHT, GAME_RESOLUTION_WIDTH};
+
+use crate::launch_options::LaunchOptions;
 
 fn main() {
-    App::new()
-        .add_plugins(
+    let launch_options = match LaunchOptions::from_arguments(std::env::args().skip(1)) {
+        Ok(launch_options) => launch_options,
+        Err(error) => {
+            eprintln!("{}", error);
+            eprintln!(
+                "Usage: street_of_zombies [--headless [NUMBER_OF_TICKS]] [--seed SEED] [--record FILE | --replay FILE]"
+            );
+            std::process::exit(2);
+        }
+    };
+
+    let mut app = App::new();
+    if let Err(error) = insert_game_session(&mut app, &launch_options) {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    }
+
+    match launch_options.headless_ticks {
+        Some(number_of_ticks) => run_headless(app, number_of_ticks),
+        None => run_windowed(app),
+    }
+}
+
+/// Insert the configuration and the random number generator of the game, and the replay to record or to play
+///
+/// # Arguments
+///
+/// * `app` - The bevy application
+/// * `launch_options` - The options given on the command line
+///
+fn insert_game_session(app: &mut App, launch_options: &LaunchOptions) -> Result<(), String> {
+    app.insert_resource(GameConfigWatcher::new(GAME_CONFIG_PATH.to_string()))
+        .insert_resource(InputBindings::load_or_default(SETTINGS_PATH)?)
+        .insert_resource(SettingsFile {
+            path: SETTINGS_PATH.to_string(),
+        });
+
+    let replay = match &launch_options.replay_path {
+        Some(path) => Some(Replay::load(path)?),
+        None => None,
+    };
+
+    // A replay is played with the seed and the configuration it was recorded with
+    let game_rng = match (&replay, launch_options.seed) {
+        (Some(replay), _) => GameRng::new(replay.get_seed()),
+        (None, Some(seed)) => GameRng::new(seed),
+        (None, None) => GameRng::default(),
+    };
+    let game_config = match &replay {
+        Some(replay) => replay.get_game_config().clone(),
+        None => GameConfig::load_or_default(GAME_CONFIG_PATH)?,
+    };
+
+    if let Some(path) = &launch_options.record_path {
+        app.insert_resource(ReplayRecorder::new(path.clone(), game_rng.get_seed(), game_config.clone()));
+    }
+    if let Some(replay) = replay {
+        app.insert_resource(ReplayPlayer::new(replay));
+    }
+    app.insert_resource(game_config).insert_resource(game_rng);
+
+    Ok(())
+}
+
+/// Launch the game in a window
+fn run_windowed(mut app: App) {
+    app.add_plugins(
             DefaultPlugins
                 .set(WindowPlugin {
                     primary_window: Some(Window {
@@ -31,9 +103,56 @@ fn main() {
                         backends: Some(Backends::VULKAN),
                         ..Default::default()
                     }),
-                    ..Default::default()
                 })
         )
-        .add_plugins(StreetOfZombiesEngine)
+        .add_plugins((StreetOfZombiesGameplay, StreetOfZombiesPresentation))
         .run();
 }
+
+/// Simulate the game without window nor GPU, then print the final score.
+/// Each tick is a single gameplay update of the fixed timestep.
+///
+/// The simulation stops at the game over, or at the end of the replay when a replay is played.
+///
+/// # Arguments
+///
+/// * `app` - The bevy application
+/// * `number_of_ticks` - The maximum number of game loop updates to simulate
+///
+fn run_headless(mut app: App, number_of_ticks: u32) {
+    app.add_plugins((MinimalPlugins, InputPlugin, StreetOfZombiesGameplay))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / GAMEPLAY_TICKS_PER_SECOND,
+        )));
+    app.finish();
+    app.cleanup();
+
+    // Nobody is there to leave the main menu
+    app.world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Playing);
+
+    let mut ticks_elapsed: u32 = 0;
+    while ticks_elapsed < number_of_ticks && !is_headless_simulation_over(&app.world) {
+        app.update();
+        ticks_elapsed += 1;
+    }
+
+    if let Some(replay_recorder) = app.world.get_resource::<ReplayRecorder>() {
+        if let Err(error) = replay_recorder.save() {
+            eprintln!("{}", error);
+        }
+    }
+
+    let score = app.world.resource::<ScoreAndInfo>();
+    println!("Seed: {}", app.world.resource::<GameRng>().get_seed());
+    println!("Ticks simulated: {}", ticks_elapsed);
+    println!("Final score: {}", score.get_score());
+}
+
+fn is_headless_simulation_over(world: &World) -> bool {
+    match world.get_resource::<ReplayPlayer>() {
+        Some(replay_player) => replay_player.is_finished(),
+        None => world.resource::<ScoreAndInfo>().is_gameover(),
+    }
+}
@@ -0,0 +1,760 @@
+use crate::game_entity::ennemies::boss::Boss;
+use crate::game_entity::ennemies::{Ennemy, EnnemyKind};
+use crate::game_entity::health::Health;
+use crate::game_entity::pickups::{Pickup, PickupKind};
+use crate::game_entity::player::Player;
+use crate::game_entity::projectiles::Projectile;
+use crate::game_system::game_state::GameState;
+use crate::game_system::input_bindings::{InputAction, InputBindings, RebindMenu};
+use crate::game_system::player_input::AimMode;
+use crate::game_system::gameplay_events::{DifficultyIncreased, EnnemyHit, PlayerHit};
+use crate::game_system::math_and_generator::GameRng;
+use crate::game_system::scoreboard::{get_difficulty_name, ScoreAndInfo};
+use crate::game_system::*;
+use crate::sprite_manager_system::*;
+use crate::{GAME_RESOLUTION_HEIGHT, GAME_RESOLUTION_WIDTH};
+
+use bevy::prelude::*;
+
+/// Size of the sprite of a pickup
+static PICKUP_SPRITE_SIZE: Vec2 = Vec2::new(16., 16.);
+/// Size of the sprite of a tank, the other characters keep the size of their sprite sheet cell
+static TANK_SPRITE_SIZE: Vec2 = Vec2::new(110., 110.);
+/// Size of the sprite of a boss
+static BOSS_SPRITE_SIZE: Vec2 = Vec2::new(200., 200.);
+/// A pickup blinks during its last seconds
+static PICKUP_BLINK_TIME: f32 = 3.0;
+/// Number of tint changes per second of the invulnerable player
+static PLAYER_FLASH_FREQUENCY: f32 = 10.0;
+/// Tint of the player during its invulnerability, every other flash
+static PLAYER_FLASH_COLOR: Color = Color::rgba(1.0, 0.3, 0.3, 0.5);
+/// Duration of the screen shake when the player is hit, in seconds
+static SCREEN_SHAKE_DURATION: f32 = 0.3;
+/// Offset of the camera during a screen shake, per health lost by the player, in pixels
+static SCREEN_SHAKE_AMPLITUDE: f32 = 8.0;
+/// Above this damage, a hit does not shake the camera any stronger
+static SCREEN_SHAKE_MAX_DAMAGE: u32 = 3;
+/// Oscillations of the camera per second during a screen shake
+static SCREEN_SHAKE_FREQUENCY: f32 = 30.0;
+/// Tint of an ennemy just hit, it goes back to its own colors afterwards
+static ENNEMY_HIT_COLOR: Color = Color::rgb(1.0, 0.2, 0.2);
+/// Speed of the return of a hit ennemy to its own colors, per second
+static ENNEMY_HIT_RECOVERY_SPEED: f32 = 6.0;
+/// Duration of the banner announcing a new difficulty level, in seconds
+static DIFFICULTY_BANNER_DURATION: f32 = 2.5;
+/// Size of the health bar of the boss, under the scoreboard
+static BOSS_HEALTH_BAR_SIZE: Vec2 = Vec2::new(600., 24.);
+static BOSS_HEALTH_BAR_COLOR: Color = Color::rgb(0.7, 0.1, 0.6);
+static BOSS_HEALTH_BAR_BACKGROUND_COLOR: Color = Color::rgba(0.1, 0.1, 0.1, 0.7);
+
+/// Everything the player sees: camera, background, sprites, animations and scoreboard.
+///
+/// The sprites are attached to the entities spawned by the `StreetOfZombiesGameplay` plugin.
+/// Their `Transform` is interpolated between the two last gameplay ticks.
+pub struct StreetOfZombiesPresentation;
+
+impl Plugin for StreetOfZombiesPresentation {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScreenShake>()
+            .init_resource::<DifficultyBanner>()
+            .add_systems(Startup, (setup, set_window_parameters))
+            .add_systems(OnEnter(GameState::MainMenu), spawn_main_menu_text)
+            .add_systems(OnExit(GameState::MainMenu), despawn_game_state_text)
+            .add_systems(OnEnter(GameState::Paused), spawn_pause_text)
+            .add_systems(OnExit(GameState::Paused), despawn_game_state_text)
+            .add_systems(OnEnter(GameState::Controls), spawn_controls_text)
+            .add_systems(OnExit(GameState::Controls), despawn_game_state_text)
+            .add_systems(Update, controls_text_system.run_if(in_state(GameState::Controls)))
+            .add_systems(Update, game_state_prompt_text_system.run_if(resource_changed::<InputBindings>()))
+            .add_systems(FixedUpdate, (
+                restore_simulated_translation.before(GameplaySimulationSet),
+                record_simulated_translation.after(GameplaySimulationSet),
+            ))
+            .add_systems(Update, (
+                attach_player_sprite,
+                attach_ennemy_sprite,
+                attach_projectile_sprite,
+                attach_pickup_sprite,
+                blink_pickup_system,
+                flash_invulnerable_player_system,
+                flash_hit_ennemy_system,
+                (start_screen_shake_system, screen_shake_system).chain(),
+                difficulty_banner_system,
+                boss_health_bar_system,
+                interpolate_translation,
+                scoreboard_text_system,
+                weapon_text_system,
+                animate_sprite_system,
+            ));
+    }
+}
+
+/// The texture atlases shared by every character of the same type
+#[derive(Resource)]
+struct CharacterTextures {
+    player: Handle<TextureAtlas>,
+    walker: Handle<TextureAtlas>,
+    runner: Handle<TextureAtlas>,
+    spitter: Handle<TextureAtlas>,
+    tank: Handle<TextureAtlas>,
+    exploder: Handle<TextureAtlas>,
+    boss: Handle<TextureAtlas>,
+}
+
+impl CharacterTextures {
+    /// Get the texture atlas of an archetype of ennemy
+    fn get_ennemy(&self, kind: EnnemyKind) -> Handle<TextureAtlas> {
+        match kind {
+            EnnemyKind::Walker => self.walker.clone(),
+            EnnemyKind::Runner => self.runner.clone(),
+            EnnemyKind::Spitter => self.spitter.clone(),
+            EnnemyKind::Tank => self.tank.clone(),
+            EnnemyKind::Exploder => self.exploder.clone(),
+            EnnemyKind::Boss => self.boss.clone(),
+        }
+    }
+}
+
+/// The translations computed by the two last gameplay ticks.
+/// The `Transform` drawn between two ticks is interpolated from them.
+#[derive(Component)]
+struct TranslationInterpolation {
+    previous: Vec3,
+    simulated: Vec3,
+}
+
+impl TranslationInterpolation {
+    fn new(translation: Vec3) -> Self {
+        TranslationInterpolation {
+            previous: translation,
+            simulated: translation,
+        }
+    }
+}
+
+/// The shake of the camera after a hit on the player.
+/// Only the presentation shakes: the camera is not part of the gameplay
+#[derive(Resource, Default)]
+struct ScreenShake {
+    remaining_time: f32,
+    /// The maximum offset of the camera, stronger for the heavy hits
+    amplitude: f32,
+}
+
+/// Time left before the banner announcing a new difficulty level disappears
+#[derive(Resource, Default)]
+struct DifficultyBanner {
+    remaining_time: f32,
+}
+
+/// Marker of the text entity displaying the banner of a new difficulty level
+#[derive(Component)]
+struct DifficultyBannerText;
+
+/// Marker of the health bar of the boss, hidden out of the boss fights
+#[derive(Component)]
+struct BossHealthBar;
+
+/// Marker of the part of the health bar of the boss showing its health left
+#[derive(Component)]
+struct BossHealthBarFill;
+
+/// Marker of the text entity displaying the phase of the boss fight, over its health bar
+#[derive(Component)]
+struct BossHealthText;
+
+/// Marker of the text entity displaying the scoreboard
+#[derive(Component)]
+struct ScoreboardText;
+
+/// Marker of the text entity displaying the weapon of the player and its amo
+#[derive(Component)]
+struct WeaponText;
+
+/// Marker of the text printed in the middle of the screen (main menu, pause, rebind screen)
+#[derive(Component)]
+struct GameStateText;
+
+/// The text of the main menu or of the pause screen, written from the keys of the player
+#[derive(Component)]
+struct GameStatePromptText(fn(&InputBindings) -> String);
+
+/// Marker of the text listing the keys of each action on the rebind screen
+#[derive(Component)]
+struct ControlsText;
+
+/// Initial setup
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    // cameras
+    commands.spawn(Camera2dBundle::default());
+
+    // Background image
+    let background_image: Handle<Image> =
+        asset_server.load("images/background_street_of_zombies.png");
+    commands.spawn(SpriteBundle {
+        texture: background_image,
+        ..Default::default()
+    });
+
+    // Keep the character textures loaded, a new zombie never shows up without its sprite
+    commands.insert_resource(CharacterTextures {
+        player: generate_texture(&asset_server, &mut texture_atlases, TextureToGenerate::PLAYER),
+        walker: generate_texture(&asset_server, &mut texture_atlases, TextureToGenerate::ZOMBIE),
+        runner: generate_texture(&asset_server, &mut texture_atlases, TextureToGenerate::RUNNER),
+        spitter: generate_texture(&asset_server, &mut texture_atlases, TextureToGenerate::SPITTER),
+        tank: generate_texture(&asset_server, &mut texture_atlases, TextureToGenerate::TANK),
+        exploder: generate_texture(&asset_server, &mut texture_atlases, TextureToGenerate::EXPLODER),
+        boss: generate_texture(&asset_server, &mut texture_atlases, TextureToGenerate::BOSS),
+    });
+
+    // Scoreboard
+    commands
+        .spawn(TextBundle::from_sections([
+            TextSection::new(
+                "Score",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 40.0,
+                    color: Color::rgb(0.5, 0.5, 1.0),
+                },
+            ),
+            TextSection::new(
+                "health",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 40.0,
+                    color: Color::rgb(0.5, 1.0, 0.5),
+                },
+            ),
+            TextSection::new(
+                "Difficulty",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 40.0,
+                    color: Color::rgb(1.0, 1.0, 1.0),
+                },
+            ),
+        ])
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(0.0),
+            left: Val::Px(0.0),
+            ..Default::default()
+        }))
+        .insert(ScoreboardText);
+
+    // Weapon of the player
+    commands
+        .spawn(TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                font_size: 30.0,
+                color: Color::rgb(1.0, 0.8, 0.3),
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(0.0),
+            left: Val::Px(0.0),
+            ..Default::default()
+        }))
+        .insert(WeaponText);
+
+    // Banner of a new difficulty level, empty until the difficulty increases
+    commands
+        .spawn(
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 50.0,
+                    color: Color::rgb(1.0, 0.3, 0.3),
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(GAME_RESOLUTION_HEIGHT / 6.),
+                left: Val::Px(GAME_RESOLUTION_WIDTH / 3.),
+                ..Default::default()
+            }),
+        )
+        .insert(DifficultyBannerText);
+
+    // Health bar of the boss, under the scoreboard
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(50.0),
+                left: Val::Px((GAME_RESOLUTION_WIDTH - BOSS_HEALTH_BAR_SIZE.x) / 2.),
+                width: Val::Px(BOSS_HEALTH_BAR_SIZE.x),
+                height: Val::Px(BOSS_HEALTH_BAR_SIZE.y),
+                ..Default::default()
+            },
+            background_color: BOSS_HEALTH_BAR_BACKGROUND_COLOR.into(),
+            visibility: Visibility::Hidden,
+            ..Default::default()
+        })
+        .insert(BossHealthBar)
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        ..Default::default()
+                    },
+                    background_color: BOSS_HEALTH_BAR_COLOR.into(),
+                    ..Default::default()
+                })
+                .insert(BossHealthBarFill);
+            parent
+                .spawn(
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                            font_size: 20.0,
+                            color: Color::rgb(1.0, 1.0, 1.0),
+                        },
+                    )
+                    .with_style(Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(5.0),
+                        ..Default::default()
+                    }),
+                )
+                .insert(BossHealthText);
+        });
+}
+
+/// Print the title of the game on the main menu
+fn spawn_main_menu_text(commands: Commands, asset_server: Res<AssetServer>, input_bindings: Res<InputBindings>) {
+    spawn_game_state_text(commands, &asset_server, &input_bindings, get_main_menu_text);
+}
+
+/// Print the pause screen
+fn spawn_pause_text(commands: Commands, asset_server: Res<AssetServer>, input_bindings: Res<InputBindings>) {
+    spawn_game_state_text(commands, &asset_server, &input_bindings, get_pause_text);
+}
+
+/// Get the text of the main menu, with the keys of the player
+fn get_main_menu_text(input_bindings: &InputBindings) -> String {
+    format!(
+        "STREET OF ZOMBIES\n - PRESS {} TO PLAY -\n - PRESS {} FOR THE CONTROLS -",
+        input_bindings.get_keys_name(InputAction::StartGame).to_uppercase(),
+        input_bindings.get_keys_name(InputAction::OpenControls).to_uppercase()
+    )
+}
+
+/// Get the text of the pause screen, with the keys of the player
+fn get_pause_text(input_bindings: &InputBindings) -> String {
+    format!(
+        "- PAUSE -\n - PRESS {} TO CONTINUE -",
+        input_bindings.get_keys_name(InputAction::Pause).to_uppercase()
+    )
+}
+
+fn spawn_game_state_text(
+    mut commands: Commands,
+    asset_server: &Res<AssetServer>,
+    input_bindings: &InputBindings,
+    get_text: fn(&InputBindings) -> String,
+) {
+    commands
+        .spawn(
+            TextBundle::from_section(
+                get_text(input_bindings),
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 40.0,
+                    color: Color::rgb(1.0, 1.0, 1.0),
+                },
+            )
+            .with_text_alignment(TextAlignment::Center)
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(GAME_RESOLUTION_HEIGHT / 3.),
+                left: Val::Px(GAME_RESOLUTION_WIDTH / 3.),
+                ..Default::default()
+            }),
+        )
+        .insert((GameStateText, GameStatePromptText(get_text)));
+}
+
+/// Write again the main menu or the pause screen once the keys of the player changed
+fn game_state_prompt_text_system(
+    input_bindings: Res<InputBindings>,
+    mut query: Query<(&mut Text, &GameStatePromptText)>,
+) {
+    for (mut text, prompt_text) in query.iter_mut() {
+        text.sections[0].value = (prompt_text.0)(&input_bindings);
+    }
+}
+
+/// Print the rebind screen, its content is written by `controls_text_system`
+fn spawn_controls_text(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn(
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 20.0,
+                    color: Color::rgb(1.0, 1.0, 1.0),
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(GAME_RESOLUTION_HEIGHT / 16.),
+                left: Val::Px(GAME_RESOLUTION_WIDTH / 4.),
+                ..Default::default()
+            }),
+        )
+        .insert((GameStateText, ControlsText));
+}
+
+/// List the keys of each action on the rebind screen, the selected action is marked with ">"
+fn controls_text_system(
+    input_bindings: Res<InputBindings>,
+    rebind_menu: Res<RebindMenu>,
+    mut query: Query<&mut Text, With<ControlsText>>,
+) {
+    if let Ok(mut text) = query.get_single_mut() {
+        let mut value = "- CONTROLS -\nUP / DOWN: SELECT - ENTER: REBIND - BACKSPACE: DEFAULT - ESCAPE: SAVE\n\n".to_string();
+
+        for action in InputAction::ALL {
+            let is_selected = action == rebind_menu.get_selected_action();
+            let keys = if is_selected && rebind_menu.is_waiting_for_key() {
+                "PRESS A KEY...".to_string()
+            } else {
+                input_bindings.get_keys_name(action)
+            };
+
+            value += &format!("{} {}: {}\n", if is_selected { ">" } else { " " }, action.get_name(), keys);
+        }
+
+        text.sections[0].value = value;
+    }
+}
+
+/// Remove the text of the main menu, of the pause screen or of the rebind screen
+fn despawn_game_state_text(mut commands: Commands, query: Query<Entity, With<GameStateText>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// This "Startup-Item" modify the Window parameter (title and no-resize)
+fn set_window_parameters(mut windows: Query<&mut Window>) {
+    if let Ok(mut window) = windows.get_single_mut() {
+        window.title = "Street of Zombies".to_string();
+        window.resizable = false;
+    }
+}
+
+/// Attach the animated sprite of the main character
+fn attach_player_sprite(
+    mut commands: Commands,
+    textures: Res<CharacterTextures>,
+    query: Query<(Entity, &Transform), Added<Player>>,
+) {
+    for (entity, transform) in query.iter() {
+        attach_character_sprite(&mut commands, entity, transform, textures.player.clone(), None);
+    }
+}
+
+/// Attach the animated sprite of each new ennemy, following its archetype. The tanks and the bosses are drawn bigger
+fn attach_ennemy_sprite(
+    mut commands: Commands,
+    textures: Res<CharacterTextures>,
+    query: Query<(Entity, &Ennemy, &Transform), Added<Ennemy>>,
+) {
+    for (entity, ennemy, transform) in query.iter() {
+        let custom_size = match ennemy.get_kind() {
+            EnnemyKind::Tank => Some(TANK_SPRITE_SIZE),
+            EnnemyKind::Boss => Some(BOSS_SPRITE_SIZE),
+            _ => None,
+        };
+        attach_character_sprite(
+            &mut commands,
+            entity,
+            transform,
+            textures.get_ennemy(ennemy.get_kind()),
+            custom_size,
+        );
+    }
+}
+
+fn attach_character_sprite(
+    commands: &mut Commands,
+    entity: Entity,
+    transform: &Transform,
+    texture_atlas: Handle<TextureAtlas>,
+    custom_size: Option<Vec2>,
+) {
+    commands
+        .entity(entity)
+        .insert(SpriteSheetBundle {
+            texture_atlas,
+            transform: *transform,
+            sprite: TextureAtlasSprite {
+                custom_size,
+                ..TextureAtlasSprite::new(1)
+            },
+            ..Default::default()
+        })
+        .insert(TranslationInterpolation::new(transform.translation))
+        .insert(AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)));
+}
+
+/// Attach a colored rectangle to each new projectile (color and size of the weapon which fired it)
+fn attach_projectile_sprite(
+    mut commands: Commands,
+    query: Query<(Entity, &Projectile, &Transform), Added<Projectile>>,
+) {
+    for (entity, projectile, transform) in query.iter() {
+        commands
+            .entity(entity)
+            .insert(SpriteBundle {
+                sprite: Sprite {
+                    color: projectile.get_color(),
+                    custom_size: Some(projectile.get_size()),
+                    ..Default::default()
+                },
+                transform: *transform,
+                ..Default::default()
+            })
+            .insert(TranslationInterpolation::new(transform.translation));
+    }
+}
+
+/// Attach a colored square to each new pickup, one color per bonus
+fn attach_pickup_sprite(mut commands: Commands, query: Query<(Entity, &Pickup, &Transform), Added<Pickup>>) {
+    for (entity, pickup, transform) in query.iter() {
+        let color = match pickup.get_kind() {
+            PickupKind::Weapon(_) => Color::rgb(1.0, 0.6, 0.0),
+            PickupKind::AmmoRefill => Color::rgb(0.9, 0.9, 0.2),
+            PickupKind::Health => Color::rgb(0.2, 1.0, 0.2),
+            PickupKind::SpeedBoost => Color::rgb(0.3, 0.6, 1.0),
+        };
+
+        commands.entity(entity).insert(SpriteBundle {
+            sprite: Sprite {
+                color,
+                custom_size: Some(PICKUP_SPRITE_SIZE),
+                ..Default::default()
+            },
+            transform: *transform,
+            ..Default::default()
+        });
+    }
+}
+
+/// The pickups blink before they disappear
+fn blink_pickup_system(mut query: Query<(&Pickup, &mut Visibility)>) {
+    for (pickup, mut visibility) in query.iter_mut() {
+        let remaining_lifetime = pickup.get_remaining_lifetime();
+
+        *visibility = if remaining_lifetime < PICKUP_BLINK_TIME && (remaining_lifetime * 8.0) as i32 % 2 == 0 {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+    }
+}
+
+/// The invulnerable player flashes until it can be hurt again
+fn flash_invulnerable_player_system(mut query: Query<(&Player, &mut TextureAtlasSprite)>) {
+    for (player, mut sprite) in query.iter_mut() {
+        let remaining_time = player.get_invulnerability_remaining_time();
+
+        sprite.color = if player.is_invulnerable() && (remaining_time * PLAYER_FLASH_FREQUENCY) as i32 % 2 == 0 {
+            PLAYER_FLASH_COLOR
+        } else {
+            Color::WHITE
+        };
+    }
+}
+
+/// The ennemies hit turn red, then go back to their own colors
+fn flash_hit_ennemy_system(
+    time: Res<Time>,
+    mut ennemy_hit_events: EventReader<EnnemyHit>,
+    mut query: Query<&mut TextureAtlasSprite, With<Ennemy>>,
+) {
+    let recovery = ENNEMY_HIT_RECOVERY_SPEED * time.delta_seconds();
+    for mut sprite in query.iter_mut() {
+        let green = (sprite.color.g() + recovery).min(1.0);
+        let blue = (sprite.color.b() + recovery).min(1.0);
+        sprite.color.set_g(green).set_b(blue);
+    }
+
+    for ennemy_hit in ennemy_hit_events.read() {
+        // A killed ennemy is already despawned
+        if let Ok(mut sprite) = query.get_mut(ennemy_hit.entity) {
+            sprite.color = ENNEMY_HIT_COLOR;
+        }
+    }
+}
+
+/// Each hit on the player restarts the shake of the camera, following the damage of the hit
+fn start_screen_shake_system(mut player_hit_events: EventReader<PlayerHit>, mut screen_shake: ResMut<ScreenShake>) {
+    let player_damage = player_hit_events.read().map(|player_hit| player_hit.damage).max();
+
+    if let Some(damage) = player_damage {
+        screen_shake.remaining_time = SCREEN_SHAKE_DURATION;
+        screen_shake.amplitude = SCREEN_SHAKE_AMPLITUDE * damage.min(SCREEN_SHAKE_MAX_DAMAGE) as f32;
+    }
+}
+
+/// Move the camera around its center while the shake lasts, the offset decreases until the end of the shake
+fn screen_shake_system(
+    time: Res<Time>,
+    mut screen_shake: ResMut<ScreenShake>,
+    mut camera_query: Query<&mut Transform, With<Camera2d>>,
+) {
+    screen_shake.remaining_time = (screen_shake.remaining_time - time.delta_seconds()).max(0.0);
+
+    let strength = screen_shake.amplitude * screen_shake.remaining_time / SCREEN_SHAKE_DURATION;
+    let phase = screen_shake.remaining_time * SCREEN_SHAKE_FREQUENCY * std::f32::consts::TAU;
+    let offset = Vec2::new(phase.sin(), (1.7 * phase).cos()) * strength;
+
+    for mut camera_transform in camera_query.iter_mut() {
+        camera_transform.translation.x = offset.x;
+        camera_transform.translation.y = offset.y;
+    }
+}
+
+/// Print the name of each new difficulty level in the middle of the screen for a few seconds
+fn difficulty_banner_system(
+    time: Res<Time>,
+    mut difficulty_events: EventReader<DifficultyIncreased>,
+    mut difficulty_banner: ResMut<DifficultyBanner>,
+    mut query: Query<&mut Text, With<DifficultyBannerText>>,
+) {
+    let Ok(mut text) = query.get_single_mut() else {
+        return;
+    };
+
+    if let Some(difficulty_increased) = difficulty_events.read().last() {
+        difficulty_banner.remaining_time = DIFFICULTY_BANNER_DURATION;
+        text.sections[0].value = format!("- {} -", get_difficulty_name(difficulty_increased.difficulty_level));
+    } else if difficulty_banner.remaining_time > 0.0 {
+        difficulty_banner.remaining_time -= time.delta_seconds();
+        if difficulty_banner.remaining_time <= 0.0 {
+            text.sections[0].value.clear();
+        }
+    }
+}
+
+/// Show the health of the boss and the phase of its fight during a boss fight, hide the health bar otherwise
+fn boss_health_bar_system(
+    boss_query: Query<(&Boss, &Health)>,
+    mut health_bar_query: Query<&mut Visibility, With<BossHealthBar>>,
+    mut health_fill_query: Query<&mut Style, With<BossHealthBarFill>>,
+    mut text_query: Query<&mut Text, With<BossHealthText>>,
+) {
+    let Ok(mut health_bar_visibility) = health_bar_query.get_single_mut() else {
+        return;
+    };
+    let Ok((boss, health)) = boss_query.get_single() else {
+        *health_bar_visibility = Visibility::Hidden;
+        return;
+    };
+
+    *health_bar_visibility = Visibility::Inherited;
+    if let Ok(mut health_fill_style) = health_fill_query.get_single_mut() {
+        health_fill_style.width = Val::Percent(100.0 * health.get_current() as f32 / health.get_max().max(1) as f32);
+    }
+    if let Ok(mut text) = text_query.get_single_mut() {
+        text.sections[0].value = format!("BOSS - PHASE {}", boss.get_phase() + 1);
+    }
+}
+
+/// Before a gameplay tick: give back to the gameplay the translation it computed during the last tick
+fn restore_simulated_translation(mut query: Query<(&mut Transform, &mut TranslationInterpolation)>) {
+    for (mut transform, mut interpolation) in query.iter_mut() {
+        transform.translation = interpolation.simulated;
+        interpolation.previous = interpolation.simulated;
+    }
+}
+
+/// After a gameplay tick: save the translation computed by the gameplay
+fn record_simulated_translation(mut query: Query<(&Transform, &mut TranslationInterpolation)>) {
+    for (transform, mut interpolation) in query.iter_mut() {
+        interpolation.simulated = transform.translation;
+    }
+}
+
+/// Draw the sprites between the two last gameplay ticks, following the time elapsed since the last tick
+fn interpolate_translation(
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&mut Transform, &TranslationInterpolation)>,
+) {
+    let overstep = fixed_time.overstep_percentage();
+
+    for (mut transform, interpolation) in query.iter_mut() {
+        transform.translation = interpolation.previous.lerp(interpolation.simulated, overstep);
+    }
+}
+
+/// Print the current score, health and difficulty level (and the seed of the game and the restart keys once it is over)
+fn scoreboard_text_system(
+    score: Res<ScoreAndInfo>,
+    game_rng: Res<GameRng>,
+    input_bindings: Res<InputBindings>,
+    player_query: Query<&Health, With<Player>>,
+    mut query: Query<(&mut Text, &mut Style), With<ScoreboardText>>,
+) {
+    let player_health = player_query
+        .get_single()
+        .map_or((0, 0), |health| (health.get_current(), health.get_max()));
+
+    if let Ok((mut text, mut style)) = query.get_single_mut() {
+        score.update_scoarboard_text(&mut text, &mut style, player_health, game_rng.get_seed(), &input_bindings);
+    }
+}
+
+/// Print the current weapon of the player, its inventory slot, its amo (magazine / reserve) and its reload state. Nothing is printed without player
+fn weapon_text_system(
+    player_query: Query<&Player>,
+    aim_mode: Res<AimMode>,
+    mut query: Query<&mut Text, With<WeaponText>>,
+) {
+    if let Ok(mut text) = query.get_single_mut() {
+        text.sections[0].value = match player_query.get_single() {
+            Ok(player) => {
+                let weapon = player.get_current_weapon();
+                let reserve_amo = match weapon.get_reserve_amo() {
+                    Some(reserve_amo) => reserve_amo.to_string(),
+                    None => "-".to_string(),
+                };
+                let weapon_state = if weapon.is_reloading() {
+                    " - RELOADING..."
+                } else if weapon.get_amo() == 0 {
+                    " - EMPTY *CLICK*"
+                } else {
+                    ""
+                };
+
+                format!(
+                    "WEAPON {}/{}: {} - AMMO: {} / {}{} - AIM: {}",
+                    player.get_current_slot() + 1,
+                    player.get_number_of_weapons(),
+                    player.get_current_weapon_kind().get_name(),
+                    weapon.get_amo(),
+                    reserve_amo,
+                    weapon_state,
+                    aim_mode.get_name()
+                )
+            }
+            Err(_) => String::new(),
+        };
+    }
+}
@@ -4,16 +4,27 @@ use bevy::sprite::TextureAtlas;
 use bevy::sprite::TextureAtlasSprite;
 use bevy::time::Timer;
 
-/// Path to the "ZOMBIE" sprite
-static ZOMBIE_ASSET_PATH: &'static str = "sprites/zombie.png";
+/// Path to the "ZOMBIE" sprite, the walker
+static ZOMBIE_ASSET_PATH: &str = "sprites/zombie.png";
+/// Path to the "RUNNER" sprite
+static RUNNER_ASSET_PATH: &str = "sprites/zombie_runner.png";
+/// Path to the "SPITTER" sprite
+static SPITTER_ASSET_PATH: &str = "sprites/zombie_spitter.png";
+/// Path to the "TANK" sprite
+static TANK_ASSET_PATH: &str = "sprites/zombie_tank.png";
+/// Path to the "EXPLODER" sprite
+static EXPLODER_ASSET_PATH: &str = "sprites/zombie_exploder.png";
+/// Path to the "BOSS" sprite
+static BOSS_ASSET_PATH: &str = "sprites/zombie_boss.png";
 /// Path to the "PLAYER" sprite
-static PLAYER_ASSET_PATH: &'static str = "sprites/woman.png";
+static PLAYER_ASSET_PATH: &str = "sprites/woman.png";
 
 /// Custom component that wraps Timer for sprite animation
 #[derive(Component)]
 pub struct AnimationTimer(pub Timer);
 
 /// Current direction of the entity (targeting up, left, right or down)
+#[allow(clippy::upper_case_acronyms)]
 #[derive(PartialEq, Debug)]
 enum TexturePositionEnum {
     DOWN,
@@ -30,23 +41,28 @@ static COLS_PER_SPRITES: usize = 8;
 pub fn animate_sprite_system(
     time: Res<Time>,
     mut query: Query<(
-        Option<&mut ennemies::Ennemy>,
-        Option<&mut player::Player>,
+        Option<&ennemies::Ennemy>,
+        Option<&player::Player>,
         &mut AnimationTimer,
         &mut TextureAtlasSprite,
     )>,
 ) {
     for (enemy, player, mut timer, mut sprite) in query.iter_mut() {
-        if let Some(mut enemy) = enemy {
+        if let Some(enemy) = enemy {
+            let facing_direction = enemy.get_moveable_interface().get_direction();
             animate_sprite(
-                &mut enemy.get_moveable_interface_mut(),
+                enemy.get_moveable_interface(),
+                facing_direction,
                 &time,
                 &mut timer.0,
                 &mut sprite,
             );
-        } else if let Some(mut player) = player {
+        } else if let Some(player) = player {
+            // The player faces its aim direction, even when walking in another direction
+            let facing_direction = player.get_fire_direction();
             animate_sprite(
-                &mut player.get_moveable_interface_mut(),
+                player.get_moveable_interface(),
+                facing_direction,
                 &time,
                 &mut timer.0,
                 &mut sprite,
@@ -57,44 +73,44 @@ pub fn animate_sprite_system(
 
 /// Animate a "Moveable Sprite"
 ///
-/// The animated sprite will change if the "Moveable Sprite" moved since last function call.
+/// The animated sprite will change if the "Moveable Sprite" moved during its last movement.
+/// The sprite faces the given direction, even when the "Moveable Sprite" does not move.
 fn animate_sprite(
-    entity: &mut MoveableSprite,
+    entity: &MoveableSprite,
+    facing_direction: Vec2,
     time: &Res<Time>,
     timer: &mut Timer,
     sprite: &mut Mut<TextureAtlasSprite>,
 ) {
     timer.tick(time.delta());
-    if timer.finished() && entity.is_sprite_moved_after_last_call() {
-        let coef_val: usize;
-
-        match generate_texture_position_from_coeff_factor(entity.get_direction()) {
-            TexturePositionEnum::DOWN => coef_val = 0 * COLS_PER_SPRITES,
-            TexturePositionEnum::LEFT => coef_val = 1 * COLS_PER_SPRITES,
-            TexturePositionEnum::RIGHT => coef_val = 2 * COLS_PER_SPRITES,
-            TexturePositionEnum::UP => coef_val = 3 * COLS_PER_SPRITES,
-        }
-
-        let calculated_index = ((sprite.index + 1) % COLS_PER_SPRITES) + coef_val;
+    if timer.finished() {
+        let coef_val: usize = match generate_texture_position_from_coeff_factor(facing_direction) {
+            TexturePositionEnum::DOWN => 0,
+            TexturePositionEnum::LEFT => COLS_PER_SPRITES,
+            TexturePositionEnum::RIGHT => 2 * COLS_PER_SPRITES,
+            TexturePositionEnum::UP => 3 * COLS_PER_SPRITES,
+        };
+
+        let step = if entity.is_sprite_moved() { 1 } else { 0 };
+        let calculated_index = ((sprite.index + step) % COLS_PER_SPRITES) + coef_val;
         sprite.index = calculated_index;
     }
 }
 
 /// This function converts the direction of the "MoveableSprite" to a TexturePositionEnum (UP, DOWN, LEFT, RIGHT)
 ///
-/// The animated sprite will change if the "Moveable Sprite" moved since last function call.
 /// # Arguments
 ///
-/// * `coeff_factor` - The direction factor as a tuple (x, y)
+/// * `coeff_factor` - The direction factor
 /// # Examples
 ///
-/// ```
-///     let generated_direction = generate_texture_position_from_coeff_factor((0.0, 1.0));
+/// ```ignore
+///     let generated_direction = generate_texture_position_from_coeff_factor(Vec2::new(0.0, 1.0));
 ///     assert_eq!(generated_direction, TexturePositionEnum::UP);
 /// ```
-fn generate_texture_position_from_coeff_factor(coeff_factor: (f32, f32)) -> TexturePositionEnum {
+fn generate_texture_position_from_coeff_factor(coeff_factor: Vec2) -> TexturePositionEnum {
     let texture_direction: TexturePositionEnum;
-    let (coeff_factor_x, coeff_factor_y) = coeff_factor;
+    let (coeff_factor_x, coeff_factor_y) = (coeff_factor.x, coeff_factor.y);
 
     let cond_1 = coeff_factor_x.abs() > coeff_factor_y.abs();
     let cond_2 = (coeff_factor_x - coeff_factor_y) >= 0.;
@@ -119,9 +135,15 @@ fn generate_texture_position_from_coeff_factor(coeff_factor: (f32, f32)) -> Text
 }
 
 /// Enumerator about the different textures available
+#[allow(clippy::upper_case_acronyms)]
 pub enum TextureToGenerate {
     PLAYER,
     ZOMBIE,
+    RUNNER,
+    SPITTER,
+    TANK,
+    EXPLODER,
+    BOSS,
 }
 
 /// Generate a texture thanks to a "TextureToGenerate"
@@ -130,12 +152,15 @@ pub fn generate_texture(
     texture_atlases: &mut ResMut<Assets<TextureAtlas>>,
     texture_type: TextureToGenerate,
 ) -> Handle<TextureAtlas> {
-    let texture_path: &str;
-
-    match texture_type {
-        TextureToGenerate::PLAYER => texture_path = PLAYER_ASSET_PATH,
-        TextureToGenerate::ZOMBIE => texture_path = ZOMBIE_ASSET_PATH,
-    }
+    let texture_path: &str = match texture_type {
+        TextureToGenerate::PLAYER => PLAYER_ASSET_PATH,
+        TextureToGenerate::ZOMBIE => ZOMBIE_ASSET_PATH,
+        TextureToGenerate::RUNNER => RUNNER_ASSET_PATH,
+        TextureToGenerate::SPITTER => SPITTER_ASSET_PATH,
+        TextureToGenerate::TANK => TANK_ASSET_PATH,
+        TextureToGenerate::EXPLODER => EXPLODER_ASSET_PATH,
+        TextureToGenerate::BOSS => BOSS_ASSET_PATH,
+    };
 
     let texture_handle = asset_server.load(texture_path);
     let generated_texture = TextureAtlas::from_grid(
@@ -156,37 +181,37 @@ mod tests {
 
     #[test]
     fn coeff_factor_to_texture_up() {
-        let generated_direction = generate_texture_position_from_coeff_factor((0.0, 1.0));
+        let generated_direction = generate_texture_position_from_coeff_factor(Vec2::new(0.0, 1.0));
         assert_eq!(generated_direction, TexturePositionEnum::UP);
     }
 
     #[test]
     fn coeff_factor_to_texture_down() {
-        let generated_direction = generate_texture_position_from_coeff_factor((0.0, -1.0));
+        let generated_direction = generate_texture_position_from_coeff_factor(Vec2::new(0.0, -1.0));
         assert_eq!(generated_direction, TexturePositionEnum::DOWN);
     }
 
     #[test]
     fn coeff_factor_to_texture_left() {
-        let generated_direction = generate_texture_position_from_coeff_factor((-1.0, 0.0));
+        let generated_direction = generate_texture_position_from_coeff_factor(Vec2::new(-1.0, 0.0));
         assert_eq!(generated_direction, TexturePositionEnum::LEFT);
     }
 
     #[test]
     fn coeff_factor_to_texture_right() {
-        let generated_direction = generate_texture_position_from_coeff_factor((1.0, 0.0));
+        let generated_direction = generate_texture_position_from_coeff_factor(Vec2::new(1.0, 0.0));
         assert_eq!(generated_direction, TexturePositionEnum::RIGHT);
     }
 
     #[test]
     fn coeff_factor_to_texture_priority_up() {
-        let generated_direction = generate_texture_position_from_coeff_factor((1.0, 1.0));
+        let generated_direction = generate_texture_position_from_coeff_factor(Vec2::new(1.0, 1.0));
         assert_eq!(generated_direction, TexturePositionEnum::UP);
     }
 
     #[test]
     fn coeff_factor_to_texture_priority_down() {
-        let generated_direction = generate_texture_position_from_coeff_factor((1.0, -1.0));
+        let generated_direction = generate_texture_position_from_coeff_factor(Vec2::new(1.0, -1.0));
         assert_eq!(generated_direction, TexturePositionEnum::DOWN);
     }
 }
@@ -0,0 +1,20 @@
+use crate::game_system::game_config::WeaponConfig;
+use crate::weapons::Firearm;
+use crate::weapons::Weapon;
+use crate::weapons::WeaponData;
+
+impl Weapon for Firearm {
+    fn new(config: &WeaponConfig) -> Self {
+        Firearm {
+            weapon_data: WeaponData::new(config),
+        }
+    }
+
+    fn get_weapon_data(&self) -> &WeaponData {
+        &self.weapon_data
+    }
+
+    fn get_weapon_data_mut(&mut self) -> &mut WeaponData {
+        &mut self.weapon_data
+    }
+}
@@ -1,34 +1,220 @@
-pub mod pistol;
+pub mod firearm;
+pub mod rocket_launcher;
+pub mod shotgun;
 
 use crate::game_entity::projectiles::Projectile;
+use crate::game_system::game_config::{ProjectileAppearanceConfig, WeaponConfig};
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-/// A Pistol is a "Weapon", single fire
-pub struct Pistol {
+/// Color of the projectiles fired by the ennemies, whatever their weapon
+static ENNEMY_PROJECTILE_COLOR: Color = Color::rgb(1.0, 0.0, 0.0);
+
+/// The data shared by every weapon: ammo, fire rate and characteristics of the projectiles
+pub struct WeaponData {
     /// Speed of the projectile
     speed: f32,
-    /// Maximum number of amo in the weapon
-    max_amo: u32,
-    /// Current amo  in the weapon
+    /// Maximum number of amo in the magazine
+    magazine_size: u32,
+    /// Current amo in the magazine
     current_amo: u32,
+    /// Amo carried outside of the magazine, None when unlimited
+    reserve_amo: Option<u32>,
+    /// Maximum number of amo carried outside of the magazine, None when unlimited
+    max_reserve_amo: Option<u32>,
+    /// Duration of a reload, in seconds
+    reload_time: f32,
+    /// Time before the end of the current reload, None when the weapon is not reloading
+    reload_remaining_time: Option<f32>,
     /// Distance of fire of the projectiles generated by the weapon
     limit_of_fire: u32,
+    /// Health removed by a projectile
+    damage: u32,
+    /// Number of different ennemies hit by a projectile before it disappears
+    pierce_count: u32,
+    /// Color and size of the projectiles, None for the default projectile
+    projectile_appearance: Option<ProjectileAppearanceConfig>,
     /// The fire rate
     initial_fire_rate: f32,
     /// The fire rate
     current_fire_rate_timer: f32,
 }
 
-/// Define a Weapon which is able to fire projectiles
+impl WeaponData {
+    /// A new weapon, its magazine is full
+    fn new(config: &WeaponConfig) -> Self {
+        WeaponData {
+            speed: config.projectile_speed,
+            magazine_size: config.magazine_size,
+            current_amo: config.magazine_size,
+            reserve_amo: config.reserve_ammo,
+            max_reserve_amo: config.reserve_ammo,
+            reload_time: config.reload_time,
+            reload_remaining_time: None,
+            limit_of_fire: config.fire_distance,
+            damage: config.damage,
+            pierce_count: config.pierce_count,
+            projectile_appearance: config.projectile_appearance,
+            initial_fire_rate: config.fire_rate,
+            current_fire_rate_timer: 0.0,
+        }
+    }
+
+    /// Create a projectile of the weapon
+    ///
+    /// # Arguments
+    ///
+    /// * `direction_to_set` - The direction of the projectile
+    /// * `initial_position_to_set` - The initial position of the projectile
+    /// * `is_ennemy` - True if the projectile is from an ennemy.
+    ///
+    fn create_projectile(&self, direction_to_set: Vec2, initial_position_to_set: Vec2, is_ennemy: bool) -> Projectile {
+        let projectile = Projectile::new(
+            self.speed,
+            direction_to_set,
+            initial_position_to_set,
+            self.limit_of_fire,
+            is_ennemy,
+        )
+        .with_damage(self.damage)
+        .with_pierce_count(self.pierce_count);
+
+        match self.projectile_appearance {
+            Some(appearance) => {
+                projectile.with_appearance(get_projectile_color(appearance.color, is_ennemy), appearance.size)
+            }
+            None => projectile,
+        }
+    }
+}
+
+/// What happened when the trigger of a weapon was pulled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FireResult {
+    /// The projectiles were launched
+    Fired,
+    /// The weapon waits for its fire rate
+    NotReady,
+    /// Nothing is launched during a reload
+    Reloading,
+    /// The magazine is empty, the reload starts if there is amo left
+    EmptyClick,
+}
+
+/// A Firearm is a "Weapon", single fire. The pistol, the submachine gun and the sniper rifle only differ by their configuration
+pub struct Firearm {
+    weapon_data: WeaponData,
+}
+
+/// A Shotgun is a "Weapon", fires a spread of pellets
+pub struct Shotgun {
+    weapon_data: WeaponData,
+    /// Number of pellets of a single shot
+    pellets: u32,
+    /// Angle between the first and the last pellet, in radians
+    spread_angle: f32,
+}
+
+/// A Rocket launcher is a "Weapon", its projectiles explode on impact
+pub struct RocketLauncher {
+    weapon_data: WeaponData,
+    /// Radius of the explosion of a rocket
+    explosion_radius: f32,
+}
+
+/// Every type of weapon
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WeaponKind {
+    Pistol,
+    Shotgun,
+    SubmachineGun,
+    SniperRifle,
+    RocketLauncher,
+}
+
+impl WeaponKind {
+    /// Get the name of the weapon, printed on the HUD
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            WeaponKind::Pistol => "PISTOL",
+            WeaponKind::Shotgun => "SHOTGUN",
+            WeaponKind::SubmachineGun => "SUBMACHINE GUN",
+            WeaponKind::SniperRifle => "SNIPER RIFLE",
+            WeaponKind::RocketLauncher => "ROCKET LAUNCHER",
+        }
+    }
+}
+
+/// Create a new weapon
+///
+/// # Arguments
+///
+/// * `kind` - The type of weapon
+/// * `config` - The characteristics of the weapon
+///
+/// # Examples
+///
+/// ```
+///     # use street_of_zombies::game_system::game_config::GameConfig;
+///     # use street_of_zombies::weapons::{create_weapon, WeaponKind};
+///     let weapon = create_weapon(WeaponKind::Shotgun, &GameConfig::default().weapons.shotgun);
+/// ```
+pub fn create_weapon(kind: WeaponKind, config: &WeaponConfig) -> Box<dyn Weapon + Send + Sync> {
+    match kind {
+        WeaponKind::Pistol | WeaponKind::SubmachineGun | WeaponKind::SniperRifle => Box::new(Firearm::new(config)),
+        WeaponKind::Shotgun => Box::new(Shotgun::new(config)),
+        WeaponKind::RocketLauncher => Box::new(RocketLauncher::new(config)),
+    }
+}
+
+/// Spawn a projectile in the game area
+///
+/// # Arguments
+///
+/// * `commands` - The bevy command interface.
+/// * `projectile` - The projectile to spawn.
+///
+pub fn spawn_projectile(commands: &mut Commands, projectile: Projectile) {
+    let initial_position = projectile.get_initial_position();
+
+    commands
+        .spawn(TransformBundle::from_transform(Transform::from_translation(initial_position.extend(0.0))))
+        .insert(projectile);
+}
+
+/// The color of a projectile: the projectiles of the ennemies are always red
+///
+/// # Arguments
+///
+/// * `weapon_color` - The color of the projectiles of the weapon, when fired by the player
+/// * `is_ennemy` - True if the projectile is from an ennemy.
+///
+pub fn get_projectile_color(weapon_color: Color, is_ennemy: bool) -> Color {
+    if is_ennemy {
+        ENNEMY_PROJECTILE_COLOR
+    } else {
+        weapon_color
+    }
+}
+
+/// Define a Weapon which is able to fire projectiles.
+/// The player and the ennemies share the same magazine and reload logic.
 pub trait Weapon {
-    /// Create a new Weapon
-    fn new(projectile_speed: f32, fire_rate: f32, max_amo: u32, fire_distance: u32) -> Self
+    /// Create a new Weapon, its magazine is full
+    fn new(config: &WeaponConfig) -> Self
     where
         Self: Sized;
 
-    /// The generic fire command. Will generate a projectile following the weapon type defined
-    /// 
+    /// Get the data shared by every weapon (ammo, fire rate, projectiles)
+    fn get_weapon_data(&self) -> &WeaponData;
+
+    /// Get the data shared by every weapon (ammo, fire rate, projectiles)
+    fn get_weapon_data_mut(&mut self) -> &mut WeaponData;
+
+    /// The generic fire command. Will generate a projectile following the weapon type defined.
+    /// Pulling the trigger with an empty magazine starts the reload.
+    ///
     /// # Arguments
     ///
     /// * `command` - The bevy command interface.
@@ -41,57 +227,131 @@ pub trait Weapon {
         &mut self,
         commands: &mut Commands,
         time: &Res<Time>,
-        direction: (f32, f32),
-        initial_pos: (f32, f32),
+        direction: Vec2,
+        initial_pos: Vec2,
         is_ennemy: bool,
-    ) {
-        if self.get_amo() > 0 && self.is_ready_to_fire(time.delta_seconds()) {
-            self.fire_with_weapon(commands, direction, initial_pos, is_ennemy);
-            self.reduce_amo();
+    ) -> FireResult {
+        if self.is_reloading() {
+            return FireResult::Reloading;
+        }
+
+        if self.get_amo() == 0 {
+            self.start_reload();
+            return FireResult::EmptyClick;
+        }
+
+        if !self.is_ready_to_fire(time.delta_seconds()) {
+            return FireResult::NotReady;
         }
+
+        self.fire_with_weapon(commands, direction, initial_pos, is_ennemy);
+        self.reduce_amo();
+        FireResult::Fired
     }
 
+    /// Spawn the projectiles of a single shot. A single projectile by default
     fn fire_with_weapon(
         &mut self,
         commands: &mut Commands,
-        direction: (f32, f32),
-        initial_pos: (f32, f32),
+        direction: Vec2,
+        initial_pos: Vec2,
         is_ennemy: bool,
     ) {
-        let (pos_x, pox_y) = initial_pos;
-        let asset_color = if is_ennemy {
-            Color::hex("FF0000").unwrap()
-        } else {
-            Color::hex("FFFFFF").unwrap()
+        spawn_projectile(commands, self.create_projectile(direction, initial_pos, is_ennemy));
+    }
+
+    /// Start to reload the magazine. Returns false when the magazine is full, when there is no amo left
+    /// or when the weapon is already reloading
+    fn start_reload(&mut self) -> bool {
+        let weapon_data = self.get_weapon_data_mut();
+
+        if weapon_data.reload_remaining_time.is_some()
+            || weapon_data.current_amo >= weapon_data.magazine_size
+            || weapon_data.reserve_amo == Some(0)
+        {
+            return false;
+        }
+
+        weapon_data.reload_remaining_time = Some(weapon_data.reload_time);
+        true
+    }
+
+    /// Advance the current reload. At the end of the reload, the magazine is filled with the reserve amo
+    ///
+    /// # Arguments
+    ///
+    /// * `time_elapsed_since_last_update` - The game time elapsed since the last update, in seconds
+    ///
+    fn update_reload(&mut self, time_elapsed_since_last_update: f32) {
+        let weapon_data = self.get_weapon_data_mut();
+
+        let reload_remaining_time = match weapon_data.reload_remaining_time {
+            Some(reload_remaining_time) => reload_remaining_time - time_elapsed_since_last_update,
+            None => return,
         };
 
-        commands
-            .spawn(SpriteBundle {
-                sprite: Sprite {
-                    color: asset_color,
-                    custom_size: Some(Vec2::new(5.0, 5.0)),
-                    ..Default::default()
-                },
-                transform: Transform::from_xyz(pos_x, pox_y, 0.0),
-                ..Default::default()
-            })
-            .insert(self.create_projectile(direction, initial_pos, is_ennemy));
+        if reload_remaining_time > 0.0 {
+            weapon_data.reload_remaining_time = Some(reload_remaining_time);
+            return;
+        }
+
+        let missing_amo = weapon_data.magazine_size.saturating_sub(weapon_data.current_amo);
+        let reloaded_amo = match weapon_data.reserve_amo {
+            Some(reserve_amo) => missing_amo.min(reserve_amo),
+            None => missing_amo,
+        };
+        weapon_data.current_amo += reloaded_amo;
+        weapon_data.reserve_amo = weapon_data.reserve_amo.map(|reserve_amo| reserve_amo - reloaded_amo);
+        weapon_data.reload_remaining_time = None;
+    }
+
+    /// Get the speed of the projectiles of the weapon, in pixels per second
+    fn get_projectile_speed(&self) -> f32 {
+        self.get_weapon_data().speed
+    }
+
+    /// Return true during a reload
+    fn is_reloading(&self) -> bool {
+        self.get_weapon_data().reload_remaining_time.is_some()
     }
 
-    /// Method to reload the weapon amo.
-    fn reload(&mut self);
+    /// Fill the reserve amo up to its maximum
+    fn refill_amo(&mut self) {
+        let weapon_data = self.get_weapon_data_mut();
+        weapon_data.reserve_amo = weapon_data.max_reserve_amo;
+    }
 
-    /// Get the number of amo available in the weapon
-    fn get_amo(&self) -> u32;
+    /// Get the number of amo available in the magazine
+    fn get_amo(&self) -> u32 {
+        self.get_weapon_data().current_amo
+    }
 
-    /// Reduce the number of amo in the weapon by 1
-    fn reduce_amo(&mut self);
+    /// Get the number of amo carried outside of the magazine, None when unlimited
+    fn get_reserve_amo(&self) -> Option<u32> {
+        self.get_weapon_data().reserve_amo
+    }
+
+    /// Reduce the number of amo in the magazine by 1
+    fn reduce_amo(&mut self) {
+        let weapon_data = self.get_weapon_data_mut();
+        weapon_data.current_amo = weapon_data.current_amo.saturating_sub(1);
+    }
 
     /// Get the fire rate of the weapon
-    fn is_ready_to_fire(&mut self, time_elapsed_since_last_update: f32) -> bool;
+    fn is_ready_to_fire(&mut self, time_elapsed_since_last_update: f32) -> bool {
+        let weapon_data = self.get_weapon_data_mut();
+        weapon_data.current_fire_rate_timer -= time_elapsed_since_last_update;
 
-    /// Create a new projectile
-    /// 
+        if weapon_data.current_fire_rate_timer < 0.0 {
+            weapon_data.current_fire_rate_timer = weapon_data.initial_fire_rate;
+            return true;
+        }
+
+        false
+    }
+
+    /// Create a new projectile, with the characteristics and the appearance of the weapon
+    ///
     /// # Arguments
     ///
     /// * `direction_to_set` - The direction of the projectile
@@ -100,8 +360,122 @@ pub trait Weapon {
     ///
     fn create_projectile(
         &self,
-        direction_to_set: (f32, f32),
-        initial_position_to_set: (f32, f32),
+        direction_to_set: Vec2,
+        initial_position_to_set: Vec2,
         is_ennemy: bool,
-    ) -> Projectile;
+    ) -> Projectile {
+        self.get_weapon_data()
+            .create_projectile(direction_to_set, initial_position_to_set, is_ennemy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+    use std::time::Duration;
+
+    fn weapon_config(magazine_size: u32, reserve_ammo: Option<u32>) -> WeaponConfig {
+        WeaponConfig {
+            projectile_speed: 500.0,
+            fire_rate: 0.0,
+            magazine_size,
+            reserve_ammo,
+            reload_time: 1.0,
+            fire_distance: 500,
+            damage: 1,
+            pierce_count: 1,
+            pellets: 1,
+            spread_angle: 0.0,
+            explosion_radius: 0.0,
+            projectile_appearance: None,
+        }
+    }
+
+    /// Pull the trigger of the pistol once, one gameplay tick after the previous pull
+    fn pull_trigger(world: &mut World, pistol: Firearm) -> (Firearm, FireResult) {
+        world.resource_mut::<Time>().advance_by(Duration::from_millis(16));
+
+        world.run_system_once_with(
+            pistol,
+            |In(mut pistol): In<Firearm>, mut commands: Commands, time: Res<Time>| {
+                let fire_result = pistol.fire_global(&mut commands, &time, Vec2::new(0., 1.), Vec2::ZERO, false);
+                (pistol, fire_result)
+            },
+        )
+    }
+
+    #[test]
+    fn empty_magazine_clicks_and_reloads() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let mut pistol = Firearm::new(&weapon_config(2, Some(3)));
+        let mut fire_results = Vec::new();
+
+        for _ in 0..4 {
+            let fire_result;
+            (pistol, fire_result) = pull_trigger(&mut world, pistol);
+            fire_results.push(fire_result);
+        }
+
+        assert_eq!(
+            fire_results,
+            [FireResult::Fired, FireResult::Fired, FireResult::EmptyClick, FireResult::Reloading]
+        );
+        assert_eq!(world.query::<&Projectile>().iter(&world).count(), 2);
+    }
+
+    #[test]
+    fn reload_takes_the_amo_from_the_reserve() {
+        let mut pistol = Firearm::new(&weapon_config(5, Some(3)));
+        for _ in 0..5 {
+            pistol.reduce_amo();
+        }
+
+        assert!(pistol.start_reload());
+        pistol.update_reload(0.6);
+        assert!(pistol.is_reloading());
+        assert_eq!(pistol.get_amo(), 0);
+
+        pistol.update_reload(0.6);
+        assert!(!pistol.is_reloading());
+        assert_eq!(pistol.get_amo(), 3);
+        assert_eq!(pistol.get_reserve_amo(), Some(0));
+
+        // Nothing left in the reserve
+        assert!(!pistol.start_reload());
+        pistol.refill_amo();
+        assert!(pistol.start_reload());
+    }
+
+    #[test]
+    fn unlimited_reserve() {
+        let mut pistol = Firearm::new(&weapon_config(3, None));
+        pistol.reduce_amo();
+
+        assert!(pistol.start_reload());
+        pistol.update_reload(1.0);
+        assert_eq!(pistol.get_amo(), 3);
+        assert_eq!(pistol.get_reserve_amo(), None);
+        // The magazine is full
+        assert!(!pistol.start_reload());
+    }
+
+    #[test]
+    fn projectiles_have_the_appearance_of_their_weapon() {
+        let weapons_config = crate::game_system::game_config::WeaponsConfig::default();
+        let appearance = weapons_config.sniper_rifle.projectile_appearance.unwrap();
+        let sniper_rifle = create_weapon(WeaponKind::SniperRifle, &weapons_config.sniper_rifle);
+
+        let projectile = sniper_rifle.create_projectile(Vec2::new(0., 1.), Vec2::ZERO, false);
+        assert_eq!((projectile.get_color(), projectile.get_size()), (appearance.color, appearance.size));
+
+        // The projectiles of the ennemies are always red
+        let projectile = sniper_rifle.create_projectile(Vec2::new(0., 1.), Vec2::ZERO, true);
+        assert_eq!(projectile.get_color(), ENNEMY_PROJECTILE_COLOR);
+
+        let rocket_launcher = create_weapon(WeaponKind::RocketLauncher, &weapons_config.rocket_launcher);
+        let rocket = rocket_launcher.create_projectile(Vec2::new(0., 1.), Vec2::ZERO, false);
+        assert_eq!(rocket.get_size(), weapons_config.rocket_launcher.projectile_appearance.unwrap().size);
+    }
 }
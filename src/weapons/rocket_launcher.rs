@@ -0,0 +1,33 @@
+use crate::game_entity::projectiles::Projectile;
+use crate::game_system::game_config::WeaponConfig;
+use crate::weapons::{RocketLauncher, Weapon, WeaponData};
+
+use bevy::prelude::*;
+
+impl Weapon for RocketLauncher {
+    fn new(config: &WeaponConfig) -> Self {
+        RocketLauncher {
+            weapon_data: WeaponData::new(config),
+            explosion_radius: config.explosion_radius,
+        }
+    }
+
+    fn get_weapon_data(&self) -> &WeaponData {
+        &self.weapon_data
+    }
+
+    fn get_weapon_data_mut(&mut self) -> &mut WeaponData {
+        &mut self.weapon_data
+    }
+
+    fn create_projectile(
+        &self,
+        direction_to_set: Vec2,
+        initial_position_to_set: Vec2,
+        is_ennemy: bool,
+    ) -> Projectile {
+        self.weapon_data
+            .create_projectile(direction_to_set, initial_position_to_set, is_ennemy)
+            .with_explosion(self.explosion_radius)
+    }
+}
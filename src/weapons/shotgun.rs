@@ -0,0 +1,87 @@
+use crate::game_system::game_config::WeaponConfig;
+use crate::game_system::math_and_generator;
+use crate::weapons::{spawn_projectile, Shotgun, Weapon, WeaponData};
+
+use bevy::prelude::*;
+
+impl Weapon for Shotgun {
+    fn new(config: &WeaponConfig) -> Self {
+        Shotgun {
+            weapon_data: WeaponData::new(config),
+            pellets: config.pellets,
+            spread_angle: config.spread_angle,
+        }
+    }
+
+    fn get_weapon_data(&self) -> &WeaponData {
+        &self.weapon_data
+    }
+
+    fn get_weapon_data_mut(&mut self) -> &mut WeaponData {
+        &mut self.weapon_data
+    }
+
+    fn fire_with_weapon(
+        &mut self,
+        commands: &mut Commands,
+        direction: Vec2,
+        initial_pos: Vec2,
+        is_ennemy: bool,
+    ) {
+        // A single pellet is fired straight ahead
+        let angle_between_pellets = if self.pellets > 1 {
+            self.spread_angle / (self.pellets - 1) as f32
+        } else {
+            0.
+        };
+        let first_pellet_angle = -angle_between_pellets * self.pellets.saturating_sub(1) as f32 / 2.;
+
+        for pellet in 0..self.pellets {
+            let angle = first_pellet_angle + pellet as f32 * angle_between_pellets;
+            let pellet_direction = math_and_generator::rotate_direction(direction, angle);
+
+            spawn_projectile(commands, self.create_projectile(pellet_direction, initial_pos, is_ennemy));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_entity::projectiles::Projectile;
+    use crate::game_entity::MoveableSpriteTrait;
+    use crate::game_system::game_config::WeaponsConfig;
+    use bevy::ecs::system::CommandQueue;
+
+    #[test]
+    fn shotgun_fires_a_spread_of_pellets() {
+        let mut world = World::new();
+        let mut command_queue = CommandQueue::default();
+        let config = WeaponsConfig::default().shotgun;
+        let mut shotgun = Shotgun::new(&config);
+
+        let mut commands = Commands::new(&mut command_queue, &world);
+        shotgun.fire_with_weapon(&mut commands, Vec2::new(0., 1.), Vec2::ZERO, false);
+        command_queue.apply(&mut world);
+
+        let mut projectile_query = world.query::<&Projectile>();
+        assert_eq!(projectile_query.iter(&world).count(), config.pellets as usize);
+    }
+
+    #[test]
+    fn shotgun_with_a_single_pellet_fires_straight_ahead() {
+        let mut world = World::new();
+        let mut command_queue = CommandQueue::default();
+        let mut config = WeaponsConfig::default().shotgun;
+        config.pellets = 1;
+        let mut shotgun = Shotgun::new(&config);
+
+        let mut commands = Commands::new(&mut command_queue, &world);
+        shotgun.fire_with_weapon(&mut commands, Vec2::new(0., 1.), Vec2::ZERO, false);
+        command_queue.apply(&mut world);
+
+        let mut projectile_query = world.query::<&Projectile>();
+        let directions: Vec<Vec2> = projectile_query.iter(&world).map(|projectile| projectile.get_moveable_interface().get_direction()).collect();
+        assert_eq!(directions, vec![Vec2::new(0., 1.)]);
+    }
+}